@@ -0,0 +1,180 @@
+//! Off-chain builder for the mini-ncn rewards tree.
+//!
+//! Produces the same roots and proofs as `tests/merkle-tree.ts`, so they verify
+//! with `mini_ncn::HashVersion::recompute` on-chain. `tests/typescript.rs`
+//! checks this against output of the TypeScript builder.
+
+use solana_program::{keccak, pubkey::Pubkey};
+
+pub type Node = [u8; 32];
+
+pub const LEAF_PREFIX: &[u8] = &[0x00];
+pub const NODE_PREFIX: &[u8] = &[0x01];
+
+/// Mirrors `mini_ncn::NATIVE_SOL_MINT`, the mint of leaves paying lamports
+pub const NATIVE_SOL_MINT: Pubkey = solana_program::system_program::ID;
+
+/// Mirrors `mini_ncn::HashVersion`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashVersion {
+    /// `keccak(owner || total_rewards_le)` and `keccak(left || right)`
+    V0,
+    /// `keccak(0x00 || owner || total_rewards_le)` and `keccak(0x01 || left || right)`
+    V1,
+    /// `keccak(0x00 || owner || mint || total_rewards_le)` and `keccak(0x01 || left || right)`
+    V2,
+}
+
+impl HashVersion {
+    /// Mirrors `mini_ncn::HashVersion::CURRENT`. `propose` stores it in the ballot
+    /// box and distributions copy it from there, so a tree must be built with the
+    /// version of the ballot box its root is voted into
+    pub const CURRENT: HashVersion = HashVersion::V2;
+
+    fn prefixes(self) -> (&'static [u8], &'static [u8]) {
+        match self {
+            HashVersion::V0 => (&[], &[]),
+            HashVersion::V1 | HashVersion::V2 => (LEAF_PREFIX, NODE_PREFIX),
+        }
+    }
+
+    /// Leaf of a rewards tree, `mint` is only committed to from V2 on
+    pub fn hash_leaf(self, owner: &Pubkey, mint: &Pubkey, total_rewards: u64) -> Node {
+        let (leaf_prefix, _) = self.prefixes();
+        let mint: &[u8] = match self {
+            HashVersion::V0 | HashVersion::V1 => &[],
+            HashVersion::V2 => mint.as_ref(),
+        };
+        keccak::hashv(&[
+            leaf_prefix,
+            owner.as_ref(),
+            mint,
+            &total_rewards.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    pub fn hash_pair(self, left: &Node, right: &Node) -> Node {
+        let (_, node_prefix) = self.prefixes();
+        keccak::hashv(&[node_prefix, left, right]).to_bytes()
+    }
+
+    /// Root of a subtree of `level` with all zero leaves, used to pad odd levels
+    pub fn empty_node(self, level: usize) -> Node {
+        let mut node = [0; 32];
+        for _ in 0..level {
+            node = self.hash_pair(&node, &node);
+        }
+        node
+    }
+
+    /// Same as `mini_ncn::HashVersion::recompute`
+    pub fn recompute(self, mut leaf: Node, proof: &[Node], index: u32) -> Node {
+        for (i, sibling) in proof.iter().enumerate() {
+            leaf = if index >> i & 1 == 0 {
+                self.hash_pair(&leaf, sibling)
+            } else {
+                self.hash_pair(sibling, &leaf)
+            };
+        }
+        leaf
+    }
+
+    pub fn verify(self, root: &Node, leaf: Node, proof: &[Node], index: u32) -> bool {
+        self.recompute(leaf, proof, index) == *root
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RewardsLeaf {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub total_rewards: u64,
+}
+
+impl RewardsLeaf {
+    pub fn hash(&self, version: HashVersion) -> Node {
+        version.hash_leaf(&self.owner, &self.mint, self.total_rewards)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MerkleTree {
+    version: HashVersion,
+    // levels[0] are the leaves, the last level is the root
+    levels: Vec<Vec<Node>>,
+}
+
+impl MerkleTree {
+    /// Builds a tree from hashed leaves. A level with an odd number of nodes
+    /// is padded with `empty_node` of that level. An empty tree has a zero root
+    /// and no proofs.
+    pub fn new(version: HashVersion, leaves: Vec<Node>) -> Self {
+        let mut levels = vec![leaves];
+
+        while levels.last().unwrap().len() > 1 {
+            let level = levels.len() - 1;
+            let nodes = levels.last().unwrap();
+            let parents = nodes
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => version.hash_pair(left, right),
+                    [left] => version.hash_pair(left, &version.empty_node(level)),
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(parents);
+        }
+
+        Self { version, levels }
+    }
+
+    pub fn from_rewards(version: HashVersion, leaves: &[RewardsLeaf]) -> Self {
+        Self::new(
+            version,
+            leaves.iter().map(|leaf| leaf.hash(version)).collect(),
+        )
+    }
+
+    pub fn version(&self) -> HashVersion {
+        self.version
+    }
+
+    pub fn root(&self) -> Node {
+        self.levels.last().unwrap().first().copied().unwrap_or([0; 32])
+    }
+
+    pub fn leaves(&self) -> &[Node] {
+        &self.levels[0]
+    }
+
+    /// Number of levels, including the leaves and the root
+    pub fn depth(&self) -> usize {
+        self.levels.len()
+    }
+
+    /// Siblings from the leaf up to the root, `None` if `index` is out of range
+    pub fn proof(&self, index: usize) -> Option<Vec<Node>> {
+        if index >= self.leaves().len() {
+            return None;
+        }
+
+        let proof = self.levels[..self.levels.len() - 1]
+            .iter()
+            .enumerate()
+            .map(|(level, nodes)| {
+                let sibling = (index >> level) ^ 1;
+                nodes
+                    .get(sibling)
+                    .copied()
+                    .unwrap_or_else(|| self.version.empty_node(level))
+            })
+            .collect();
+
+        Some(proof)
+    }
+
+    pub fn verify(&self, leaf: Node, proof: &[Node], index: u32) -> bool {
+        self.version.verify(&self.root(), leaf, proof, index)
+    }
+}
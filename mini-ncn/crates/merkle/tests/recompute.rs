@@ -0,0 +1,132 @@
+use mini_ncn_merkle::{HashVersion, MerkleTree, RewardsLeaf};
+use proptest::prelude::*;
+use solana_program::pubkey::Pubkey;
+
+fn rewards_leaves() -> impl Strategy<Value = Vec<RewardsLeaf>> {
+    prop::collection::vec(
+        (any::<[u8; 32]>(), any::<[u8; 32]>(), any::<u64>()).prop_map(
+            |(owner, mint, total_rewards)| RewardsLeaf {
+                owner: Pubkey::new_from_array(owner),
+                mint: Pubkey::new_from_array(mint),
+                total_rewards,
+            },
+        ),
+        1..100,
+    )
+}
+
+fn hash_version() -> impl Strategy<Value = HashVersion> {
+    prop_oneof![
+        Just(HashVersion::V0),
+        Just(HashVersion::V1),
+        Just(HashVersion::V2)
+    ]
+}
+
+fn on_chain(version: HashVersion) -> mini_ncn::HashVersion {
+    match version {
+        HashVersion::V0 => mini_ncn::HashVersion::V0,
+        HashVersion::V1 => mini_ncn::HashVersion::V1,
+        HashVersion::V2 => mini_ncn::HashVersion::V2,
+    }
+}
+
+proptest! {
+    #[test]
+    fn proofs_verify_on_chain(leaves in rewards_leaves(), version in hash_version()) {
+        let tree = MerkleTree::from_rewards(version, &leaves);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            prop_assert_eq!(proof.len(), tree.depth() - 1);
+
+            let on_chain_leaf = on_chain(version).hash_leaf(&leaf.owner, &leaf.mint, leaf.total_rewards);
+            prop_assert_eq!(on_chain_leaf, leaf.hash(version));
+            prop_assert_eq!(
+                on_chain(version).recompute(on_chain_leaf, &proof, index as u32),
+                tree.root()
+            );
+            prop_assert!(tree.verify(leaf.hash(version), &proof, index as u32));
+        }
+    }
+
+    #[test]
+    fn v0_matches_legacy_recompute(leaves in rewards_leaves()) {
+        let tree = MerkleTree::from_rewards(HashVersion::V0, &leaves);
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.proof(index).unwrap();
+            prop_assert_eq!(
+                mini_ncn::recompute(leaf.hash(HashVersion::V0), &proof, index as u32),
+                tree.root()
+            );
+        }
+    }
+
+    #[test]
+    fn tampered_leaves_do_not_verify(
+        leaves in rewards_leaves(),
+        version in hash_version(),
+        index in any::<prop::sample::Index>(),
+    ) {
+        let tree = MerkleTree::from_rewards(version, &leaves);
+        let index = index.index(leaves.len());
+        let proof = tree.proof(index).unwrap();
+
+        let leaf = &leaves[index];
+        let tampered = version.hash_leaf(&leaf.owner, &leaf.mint, leaf.total_rewards.wrapping_add(1));
+        prop_assert_ne!(on_chain(version).recompute(tampered, &proof, index as u32), tree.root());
+        prop_assert!(!tree.verify(tampered, &proof, index as u32));
+    }
+}
+
+#[test]
+fn current_version_matches_on_chain() {
+    assert_eq!(on_chain(HashVersion::CURRENT), mini_ncn::HashVersion::CURRENT);
+}
+
+// a proof for one mint does not verify for another
+#[test]
+fn v2_leaves_commit_to_the_mint() {
+    let owner = Pubkey::new_unique();
+    let (mint, other_mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let leaves = vec![
+        RewardsLeaf {
+            owner,
+            mint,
+            total_rewards: 100,
+        },
+        RewardsLeaf {
+            owner: Pubkey::new_unique(),
+            mint: other_mint,
+            total_rewards: 200,
+        },
+    ];
+    let tree = MerkleTree::from_rewards(HashVersion::V2, &leaves);
+    let proof = tree.proof(0).unwrap();
+
+    assert!(tree.verify(leaves[0].hash(HashVersion::V2), &proof, 0));
+    let other_leaf = HashVersion::V2.hash_leaf(&owner, &other_mint, 100);
+    assert!(!tree.verify(other_leaf, &proof, 0));
+}
+
+#[test]
+fn odd_levels_are_padded_with_empty_nodes() {
+    let version = HashVersion::V1;
+    let leaves: Vec<_> = (1..=3u8).map(|i| [i; 32]).collect();
+    let tree = MerkleTree::new(version, leaves.clone());
+
+    let expected = version.hash_pair(
+        &version.hash_pair(&leaves[0], &leaves[1]),
+        &version.hash_pair(&leaves[2], &version.empty_node(0)),
+    );
+    assert_eq!(tree.root(), expected);
+    assert_eq!(
+        tree.proof(2).unwrap(),
+        vec![
+            version.empty_node(0),
+            version.hash_pair(&leaves[0], &leaves[1])
+        ]
+    );
+    assert_eq!(tree.proof(3), None);
+}
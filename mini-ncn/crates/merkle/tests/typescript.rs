@@ -0,0 +1,102 @@
+use mini_ncn_merkle::{HashVersion, MerkleTree, Node, RewardsLeaf};
+use solana_program::pubkey::Pubkey;
+
+// roots and proofs printed by `buildRewardsTree` of tests/rewards-tree.ts for
+// `leaves(count)`, proofs in leaf order
+const FIXTURES: &[(usize, &str, &[&[&str]])] = &[
+    (
+        1,
+        "c722cf91988772962db3022e5e5895b9888230d99b4708a9ba4eb3e91a59c88a",
+        &[&[]],
+    ),
+    (
+        3,
+        "7f2726375bc4bcf6f8406a71ed9a3e1eb653f98a9fc5b8764bdf24bbe82dcb10",
+        &[
+            &[
+                "9988f28fe112d4ac7cba07314719769ad7ccf28959da76290413b64fc456e9e2",
+                "cf92a04b70c223aefcda960371e40db09707aacc6e66c2e25e4272fd58c755b5",
+            ],
+            &[
+                "c722cf91988772962db3022e5e5895b9888230d99b4708a9ba4eb3e91a59c88a",
+                "cf92a04b70c223aefcda960371e40db09707aacc6e66c2e25e4272fd58c755b5",
+            ],
+            &[
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "98c41da7cd0911d017693b725951fce0c9df117ef802c550c2dc4aee852bc502",
+            ],
+        ],
+    ),
+    (
+        5,
+        "9371a89f439006788095953645c0abb9b9b81f0159aec8f3e6387f2a85365abc",
+        &[
+            &[
+                "9988f28fe112d4ac7cba07314719769ad7ccf28959da76290413b64fc456e9e2",
+                "aec8f8d97cb7ab83e932d92207341c7d4f1bb6e3d283118c20fd8b0b9ea07cbe",
+                "929e468522fe351f13355dd956464d53351f268f04374db62fdf16298c258b7d",
+            ],
+            &[
+                "c722cf91988772962db3022e5e5895b9888230d99b4708a9ba4eb3e91a59c88a",
+                "aec8f8d97cb7ab83e932d92207341c7d4f1bb6e3d283118c20fd8b0b9ea07cbe",
+                "929e468522fe351f13355dd956464d53351f268f04374db62fdf16298c258b7d",
+            ],
+            &[
+                "e6c5bd762e48d79a639ad3a4188c654e25de65fc52d7b9d27c78f7c4a30cddd1",
+                "98c41da7cd0911d017693b725951fce0c9df117ef802c550c2dc4aee852bc502",
+                "929e468522fe351f13355dd956464d53351f268f04374db62fdf16298c258b7d",
+            ],
+            &[
+                "ac7e022f004a96ff6c1ea4ce27f386f06eac5f77a3a4d5971a83cadbbc3bed11",
+                "98c41da7cd0911d017693b725951fce0c9df117ef802c550c2dc4aee852bc502",
+                "929e468522fe351f13355dd956464d53351f268f04374db62fdf16298c258b7d",
+            ],
+            &[
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "c07a1e8b7e0057673fdc2affe190d8a960c5fe615663f27b7ce84f3d93ef92a6",
+                "96ac70d9783083c576da7d5e5bd554ec199780e1d8bc8ae5adaf9a9b0ffb40c9",
+            ],
+        ],
+    ),
+];
+
+// owner [i + 1; 32], mint [0xee; 32] and 1000 * (i + 1) for leaf i
+fn leaves(count: usize) -> Vec<RewardsLeaf> {
+    (0..count)
+        .map(|i| RewardsLeaf {
+            owner: Pubkey::new_from_array([i as u8 + 1; 32]),
+            mint: Pubkey::new_from_array([0xee; 32]),
+            total_rewards: 1000 * (i as u64 + 1),
+        })
+        .collect()
+}
+
+fn node(hex: &str) -> Node {
+    let mut node = [0; 32];
+    for (i, byte) in node.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap();
+    }
+    node
+}
+
+#[test]
+fn matches_the_typescript_builder() {
+    for &(count, root, proofs) in FIXTURES {
+        let tree = MerkleTree::from_rewards(HashVersion::V2, &leaves(count));
+        assert_eq!(tree.root(), node(root), "root of {count} leaves");
+
+        for (index, proof) in proofs.iter().enumerate() {
+            let proof: Vec<Node> = proof.iter().map(|hex| node(hex)).collect();
+            assert_eq!(tree.proof(index), Some(proof), "proof {index} of {count} leaves");
+        }
+        assert_eq!(tree.proof(count), None);
+    }
+}
+
+#[test]
+fn empty_trees_have_no_proofs() {
+    let tree = MerkleTree::new(HashVersion::V2, Vec::new());
+    assert_eq!(tree.root(), [0; 32]);
+    assert!(tree.leaves().is_empty());
+    assert_eq!(tree.proof(0), None);
+}
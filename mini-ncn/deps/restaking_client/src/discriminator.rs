@@ -0,0 +1,106 @@
+use std::io::{Error, ErrorKind};
+
+use crate::accounts::{
+    Config, Ncn, NcnOperatorState, NcnVaultSlasherTicket, NcnVaultTicket, Operator,
+    OperatorVaultTicket,
+};
+
+/// Value of the leading `discriminator: u64` of a restaking program account.
+pub trait AccountDiscriminator {
+    const DISCRIMINATOR: u64;
+}
+
+pub const CONFIG_DISCRIMINATOR: u64 = 1;
+pub const NCN_DISCRIMINATOR: u64 = 2;
+pub const OPERATOR_DISCRIMINATOR: u64 = 3;
+pub const NCN_OPERATOR_STATE_DISCRIMINATOR: u64 = 4;
+pub const OPERATOR_VAULT_TICKET_DISCRIMINATOR: u64 = 5;
+pub const NCN_VAULT_TICKET_DISCRIMINATOR: u64 = 6;
+pub const NCN_VAULT_SLASHER_TICKET_DISCRIMINATOR: u64 = 7;
+
+impl AccountDiscriminator for Config {
+    const DISCRIMINATOR: u64 = CONFIG_DISCRIMINATOR;
+}
+
+impl AccountDiscriminator for Ncn {
+    const DISCRIMINATOR: u64 = NCN_DISCRIMINATOR;
+}
+
+impl AccountDiscriminator for Operator {
+    const DISCRIMINATOR: u64 = OPERATOR_DISCRIMINATOR;
+}
+
+impl AccountDiscriminator for NcnOperatorState {
+    const DISCRIMINATOR: u64 = NCN_OPERATOR_STATE_DISCRIMINATOR;
+}
+
+impl AccountDiscriminator for OperatorVaultTicket {
+    const DISCRIMINATOR: u64 = OPERATOR_VAULT_TICKET_DISCRIMINATOR;
+}
+
+impl AccountDiscriminator for NcnVaultTicket {
+    const DISCRIMINATOR: u64 = NCN_VAULT_TICKET_DISCRIMINATOR;
+}
+
+impl AccountDiscriminator for NcnVaultSlasherTicket {
+    const DISCRIMINATOR: u64 = NCN_VAULT_SLASHER_TICKET_DISCRIMINATOR;
+}
+
+/// Reads the leading discriminator, `None` if the data is too short to hold one.
+pub fn account_discriminator(data: &[u8]) -> Option<u64> {
+    data.get(..8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+macro_rules! account_enum {
+    ($($account:ident => $discriminator:ident,)*) => {
+        /// Any restaking program account, decoded according to its discriminator.
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        pub enum JitoRestakingAccount {
+            $($account($account),)*
+        }
+
+        impl JitoRestakingAccount {
+            /// Decodes `data` as the account type its discriminator names.
+            /// Unlike the generated `from_bytes`, fails on unknown discriminators.
+            pub fn try_from_bytes(data: &[u8]) -> Result<Self, Error> {
+                match account_discriminator(data) {
+                    $(Some($discriminator) => $account::from_bytes(data).map(Self::$account),)*
+                    discriminator => Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("unknown restaking account discriminator {discriminator:?}"),
+                    )),
+                }
+            }
+
+            pub fn discriminator(&self) -> u64 {
+                match self {
+                    $(Self::$account(_) => $discriminator,)*
+                }
+            }
+        }
+
+        $(
+            impl TryFrom<JitoRestakingAccount> for $account {
+                type Error = JitoRestakingAccount;
+
+                fn try_from(account: JitoRestakingAccount) -> Result<Self, Self::Error> {
+                    match account {
+                        JitoRestakingAccount::$account(account) => Ok(account),
+                        account => Err(account),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+account_enum! {
+    Config => CONFIG_DISCRIMINATOR,
+    Ncn => NCN_DISCRIMINATOR,
+    Operator => OPERATOR_DISCRIMINATOR,
+    NcnOperatorState => NCN_OPERATOR_STATE_DISCRIMINATOR,
+    OperatorVaultTicket => OPERATOR_VAULT_TICKET_DISCRIMINATOR,
+    NcnVaultTicket => NCN_VAULT_TICKET_DISCRIMINATOR,
+    NcnVaultSlasherTicket => NCN_VAULT_SLASHER_TICKET_DISCRIMINATOR,
+}
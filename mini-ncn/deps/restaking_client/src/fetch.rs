@@ -0,0 +1,246 @@
+//! Fetching and decoding of restaking program accounts.
+//!
+//! The functions are generic over [`AccountFetcher`], which is implemented for
+//! `RpcClient`. Any other source of accounts, e.g. a mock in tests, only needs
+//! to implement `get_multiple_accounts`.
+
+use std::io::{Error, ErrorKind};
+
+use borsh::BorshDeserialize;
+use solana_program::pubkey::Pubkey;
+
+use crate::{
+    accounts::{
+        account_discriminator, AccountDiscriminator, Config, Ncn, NcnOperatorState,
+        NcnVaultSlasherTicket, NcnVaultTicket, Operator, OperatorVaultTicket,
+    },
+    programs::JITO_RESTAKING_ID,
+};
+
+/// Most accounts `getMultipleAccounts` returns in one request
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// An account as returned by the RPC, before decoding.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RawAccount {
+    pub lamports: u64,
+    pub owner: Pubkey,
+    pub data: Vec<u8>,
+}
+
+pub trait AccountFetcher {
+    /// One entry per address, in the same order, `None` if the account does not exist.
+    fn get_multiple_accounts(&self, addresses: &[Pubkey])
+        -> Result<Vec<Option<RawAccount>>, Error>;
+}
+
+impl AccountFetcher for solana_rpc_client::rpc_client::RpcClient {
+    fn get_multiple_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<RawAccount>>, Error> {
+        let mut accounts = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+            let fetched =
+                solana_rpc_client::rpc_client::RpcClient::get_multiple_accounts(self, chunk)
+                    .map_err(Error::other)?;
+            accounts.extend(fetched.into_iter().map(|account| {
+                account.map(|account| RawAccount {
+                    lamports: account.lamports,
+                    owner: account.owner,
+                    data: account.data,
+                })
+            }));
+        }
+        Ok(accounts)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DecodedAccount<T> {
+    pub address: Pubkey,
+    pub account: RawAccount,
+    pub data: T,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MaybeAccount<T> {
+    Exists(DecodedAccount<T>),
+    NotFound(Pubkey),
+}
+
+impl<T> MaybeAccount<T> {
+    /// Errors with `ErrorKind::NotFound` if the account does not exist.
+    pub fn into_result(self) -> Result<DecodedAccount<T>, Error> {
+        match self {
+            MaybeAccount::Exists(account) => Ok(account),
+            MaybeAccount::NotFound(address) => Err(Error::new(
+                ErrorKind::NotFound,
+                format!("account {address} not found"),
+            )),
+        }
+    }
+}
+
+/// Checks the owner and the discriminator before deserializing.
+pub fn decode_account<T: AccountDiscriminator + BorshDeserialize>(
+    address: Pubkey,
+    account: RawAccount,
+) -> Result<DecodedAccount<T>, Error> {
+    if account.owner != JITO_RESTAKING_ID {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("account {address} is owned by {}", account.owner),
+        ));
+    }
+
+    let discriminator = account_discriminator(&account.data);
+    if discriminator != Some(T::DISCRIMINATOR) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "account {address} has discriminator {discriminator:?}, expected {}",
+                T::DISCRIMINATOR
+            ),
+        ));
+    }
+
+    let data = T::deserialize(&mut account.data.as_slice())?;
+    Ok(DecodedAccount {
+        address,
+        account,
+        data,
+    })
+}
+
+pub fn fetch_all_maybe_accounts<T: AccountDiscriminator + BorshDeserialize>(
+    rpc: &impl AccountFetcher,
+    addresses: &[Pubkey],
+) -> Result<Vec<MaybeAccount<T>>, Error> {
+    let accounts = rpc.get_multiple_accounts(addresses)?;
+    if accounts.len() != addresses.len() {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "fetched {} accounts for {} addresses",
+                accounts.len(),
+                addresses.len()
+            ),
+        ));
+    }
+
+    addresses
+        .iter()
+        .zip(accounts)
+        .map(|(address, account)| match account {
+            Some(account) => decode_account(*address, account).map(MaybeAccount::Exists),
+            None => Ok(MaybeAccount::NotFound(*address)),
+        })
+        .collect()
+}
+
+pub fn fetch_all_accounts<T: AccountDiscriminator + BorshDeserialize>(
+    rpc: &impl AccountFetcher,
+    addresses: &[Pubkey],
+) -> Result<Vec<DecodedAccount<T>>, Error> {
+    fetch_all_maybe_accounts(rpc, addresses)?
+        .into_iter()
+        .map(MaybeAccount::into_result)
+        .collect()
+}
+
+pub fn fetch_maybe_account<T: AccountDiscriminator + BorshDeserialize>(
+    rpc: &impl AccountFetcher,
+    address: &Pubkey,
+) -> Result<MaybeAccount<T>, Error> {
+    Ok(fetch_all_maybe_accounts(rpc, &[*address])?.remove(0))
+}
+
+pub fn fetch_account<T: AccountDiscriminator + BorshDeserialize>(
+    rpc: &impl AccountFetcher,
+    address: &Pubkey,
+) -> Result<DecodedAccount<T>, Error> {
+    fetch_maybe_account(rpc, address)?.into_result()
+}
+
+macro_rules! fetch_functions {
+    ($account:ty, $fetch:ident, $fetch_maybe:ident, $fetch_all:ident, $fetch_all_maybe:ident) => {
+        pub fn $fetch(
+            rpc: &impl AccountFetcher,
+            address: &Pubkey,
+        ) -> Result<DecodedAccount<$account>, Error> {
+            fetch_account(rpc, address)
+        }
+
+        pub fn $fetch_maybe(
+            rpc: &impl AccountFetcher,
+            address: &Pubkey,
+        ) -> Result<MaybeAccount<$account>, Error> {
+            fetch_maybe_account(rpc, address)
+        }
+
+        pub fn $fetch_all(
+            rpc: &impl AccountFetcher,
+            addresses: &[Pubkey],
+        ) -> Result<Vec<DecodedAccount<$account>>, Error> {
+            fetch_all_accounts(rpc, addresses)
+        }
+
+        pub fn $fetch_all_maybe(
+            rpc: &impl AccountFetcher,
+            addresses: &[Pubkey],
+        ) -> Result<Vec<MaybeAccount<$account>>, Error> {
+            fetch_all_maybe_accounts(rpc, addresses)
+        }
+    };
+}
+
+fetch_functions!(
+    Config,
+    fetch_config,
+    fetch_maybe_config,
+    fetch_all_config,
+    fetch_all_maybe_config
+);
+fetch_functions!(
+    Ncn,
+    fetch_ncn,
+    fetch_maybe_ncn,
+    fetch_all_ncn,
+    fetch_all_maybe_ncn
+);
+fetch_functions!(
+    Operator,
+    fetch_operator,
+    fetch_maybe_operator,
+    fetch_all_operator,
+    fetch_all_maybe_operator
+);
+fetch_functions!(
+    NcnOperatorState,
+    fetch_ncn_operator_state,
+    fetch_maybe_ncn_operator_state,
+    fetch_all_ncn_operator_state,
+    fetch_all_maybe_ncn_operator_state
+);
+fetch_functions!(
+    OperatorVaultTicket,
+    fetch_operator_vault_ticket,
+    fetch_maybe_operator_vault_ticket,
+    fetch_all_operator_vault_ticket,
+    fetch_all_maybe_operator_vault_ticket
+);
+fetch_functions!(
+    NcnVaultTicket,
+    fetch_ncn_vault_ticket,
+    fetch_maybe_ncn_vault_ticket,
+    fetch_all_ncn_vault_ticket,
+    fetch_all_maybe_ncn_vault_ticket
+);
+fetch_functions!(
+    NcnVaultSlasherTicket,
+    fetch_ncn_vault_slasher_ticket,
+    fetch_maybe_ncn_vault_slasher_ticket,
+    fetch_all_ncn_vault_slasher_ticket,
+    fetch_all_maybe_ncn_vault_slasher_ticket
+);
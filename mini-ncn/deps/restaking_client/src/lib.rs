@@ -1,14 +1,25 @@
+//! Client of the Jito restaking program.
+//!
+//! The `fetch` feature adds the `fetch` module, which fetches and decodes
+//! accounts through a `fetch::AccountFetcher`, implemented for `RpcClient`.
 #![allow(clippy::all)]
 #![allow(clippy::nursery)]
 #![allow(clippy::integer_division)]
 #![allow(clippy::arithmetic_side_effects)]
 #![allow(clippy::style)]
 #![allow(clippy::perf)]
+mod discriminator;
+#[cfg(feature = "fetch")]
+pub mod fetch;
 mod generated;
+pub mod pda;
+mod slot_toggle;
+pub mod zero_copy;
 
 use generated::*;
 
 pub mod accounts {
+    pub use super::discriminator::*;
     pub use super::generated::accounts::*;
 }
 
@@ -22,6 +33,7 @@ pub mod errors {
 
 pub mod types {
     pub use super::generated::types::*;
+    pub use super::slot_toggle::SlotToggleState;
 }
 
 pub mod programs {
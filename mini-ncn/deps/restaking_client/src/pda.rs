@@ -0,0 +1,66 @@
+//! Seeds and address derivation of the restaking program accounts.
+
+use solana_program::pubkey::Pubkey;
+
+use crate::programs::JITO_RESTAKING_ID;
+
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const NCN_SEED: &[u8] = b"ncn";
+pub const OPERATOR_SEED: &[u8] = b"operator";
+pub const NCN_OPERATOR_STATE_SEED: &[u8] = b"ncn_operator_state";
+pub const NCN_VAULT_TICKET_SEED: &[u8] = b"ncn_vault_ticket";
+pub const OPERATOR_VAULT_TICKET_SEED: &[u8] = b"operator_vault_ticket";
+pub const NCN_VAULT_SLASHER_TICKET_SEED: &[u8] = b"ncn_slasher_ticket";
+
+pub fn find_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &JITO_RESTAKING_ID)
+}
+
+pub fn find_ncn_address(base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NCN_SEED, base.as_ref()], &JITO_RESTAKING_ID)
+}
+
+pub fn find_operator_address(base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[OPERATOR_SEED, base.as_ref()], &JITO_RESTAKING_ID)
+}
+
+pub fn find_ncn_operator_state_address(ncn: &Pubkey, operator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[NCN_OPERATOR_STATE_SEED, ncn.as_ref(), operator.as_ref()],
+        &JITO_RESTAKING_ID,
+    )
+}
+
+pub fn find_ncn_vault_ticket_address(ncn: &Pubkey, vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[NCN_VAULT_TICKET_SEED, ncn.as_ref(), vault.as_ref()],
+        &JITO_RESTAKING_ID,
+    )
+}
+
+pub fn find_operator_vault_ticket_address(operator: &Pubkey, vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            OPERATOR_VAULT_TICKET_SEED,
+            operator.as_ref(),
+            vault.as_ref(),
+        ],
+        &JITO_RESTAKING_ID,
+    )
+}
+
+pub fn find_ncn_vault_slasher_ticket_address(
+    ncn: &Pubkey,
+    vault: &Pubkey,
+    slasher: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            NCN_VAULT_SLASHER_TICKET_SEED,
+            ncn.as_ref(),
+            vault.as_ref(),
+            slasher.as_ref(),
+        ],
+        &JITO_RESTAKING_ID,
+    )
+}
@@ -0,0 +1,77 @@
+use crate::{errors::JitoRestakingError, types::SlotToggle, zero_copy::SlotToggleRef};
+
+/// State of a [`SlotToggle`] at a given slot, as computed by the restaking program.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SlotToggleState {
+    Inactive,
+    WarmUp,
+    Active,
+    Cooldown,
+}
+
+impl SlotToggle {
+    /// A toggle warms up during the epoch it was added in and is active from the next epoch on.
+    /// Likewise it cools down during the epoch it was removed in.
+    pub fn state(
+        &self,
+        slot: u64,
+        epoch_length: u64,
+    ) -> Result<SlotToggleState, JitoRestakingError> {
+        toggle_state(self.slot_added, self.slot_removed, slot, epoch_length)
+    }
+
+    pub fn is_active(&self, slot: u64, epoch_length: u64) -> Result<bool, JitoRestakingError> {
+        Ok(self.state(slot, epoch_length)? == SlotToggleState::Active)
+    }
+}
+
+impl SlotToggleRef {
+    /// See [`SlotToggle::state`].
+    pub fn state(
+        &self,
+        slot: u64,
+        epoch_length: u64,
+    ) -> Result<SlotToggleState, JitoRestakingError> {
+        toggle_state(
+            self.slot_added.into(),
+            self.slot_removed.into(),
+            slot,
+            epoch_length,
+        )
+    }
+
+    pub fn is_active(&self, slot: u64, epoch_length: u64) -> Result<bool, JitoRestakingError> {
+        Ok(self.state(slot, epoch_length)? == SlotToggleState::Active)
+    }
+}
+
+fn toggle_state(
+    slot_added: u64,
+    slot_removed: u64,
+    slot: u64,
+    epoch_length: u64,
+) -> Result<SlotToggleState, JitoRestakingError> {
+    let current_epoch = slot
+        .checked_div(epoch_length)
+        .ok_or(JitoRestakingError::DivisionByZero)?;
+    let epoch_added = slot_added
+        .checked_div(epoch_length)
+        .ok_or(JitoRestakingError::DivisionByZero)?;
+    let epoch_removed = slot_removed
+        .checked_div(epoch_length)
+        .ok_or(JitoRestakingError::DivisionByZero)?;
+
+    let state = if slot_added > slot_removed {
+        if current_epoch > epoch_added {
+            SlotToggleState::Active
+        } else {
+            SlotToggleState::WarmUp
+        }
+    } else if current_epoch > epoch_removed {
+        SlotToggleState::Inactive
+    } else {
+        SlotToggleState::Cooldown
+    };
+
+    Ok(state)
+}
@@ -0,0 +1,197 @@
+//! Zero-copy views of restaking program accounts.
+//!
+//! Each `*Ref` mirrors the Borsh layout of its account up to the reserved
+//! bytes. Its fields only hold byte arrays, so it can be borrowed straight from
+//! account data at any alignment instead of deserializing a copy.
+
+use std::{
+    io::{Error, ErrorKind},
+    mem::size_of,
+};
+
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+
+use crate::accounts::{
+    account_discriminator, AccountDiscriminator, CONFIG_DISCRIMINATOR, NCN_DISCRIMINATOR,
+    NCN_OPERATOR_STATE_DISCRIMINATOR, NCN_VAULT_SLASHER_TICKET_DISCRIMINATOR,
+    NCN_VAULT_TICKET_DISCRIMINATOR, OPERATOR_DISCRIMINATOR, OPERATOR_VAULT_TICKET_DISCRIMINATOR,
+};
+
+/// Little-endian `u64` without alignment.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Pod, Zeroable)]
+pub struct PodU64(pub [u8; 8]);
+
+impl From<PodU64> for u64 {
+    fn from(value: PodU64) -> Self {
+        u64::from_le_bytes(value.0)
+    }
+}
+
+/// Little-endian `u16` without alignment.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Pod, Zeroable)]
+pub struct PodU16(pub [u8; 2]);
+
+impl From<PodU16> for u16 {
+    fn from(value: PodU16) -> Self {
+        u16::from_le_bytes(value.0)
+    }
+}
+
+/// A view that can be borrowed from the data of its account.
+pub trait AccountRef: AccountDiscriminator + Pod {
+    /// Borrows the view from `data`, checking the discriminator and length.
+    fn try_from_bytes(data: &[u8]) -> Result<&Self, Error> {
+        let discriminator = account_discriminator(data);
+        if discriminator != Some(Self::DISCRIMINATOR) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "account has discriminator {discriminator:?}, expected {}",
+                    Self::DISCRIMINATOR
+                ),
+            ));
+        }
+
+        data.get(..size_of::<Self>())
+            .and_then(|data| bytemuck::try_from_bytes(data).ok())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "account data is too short"))
+    }
+}
+
+macro_rules! account_ref {
+    ($($account:ident => $discriminator:ident,)*) => {
+        $(
+            impl AccountDiscriminator for $account {
+                const DISCRIMINATOR: u64 = $discriminator;
+            }
+
+            impl AccountRef for $account {}
+        )*
+    };
+}
+
+/// View of a [`SlotToggle`](crate::types::SlotToggle).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct SlotToggleRef {
+    pub slot_added: PodU64,
+    pub slot_removed: PodU64,
+    pub reserved: [u8; 32],
+}
+
+/// View of a [`Config`](crate::accounts::Config).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct ConfigRef {
+    pub discriminator: PodU64,
+    pub admin: Pubkey,
+    pub vault_program: Pubkey,
+    pub ncn_count: PodU64,
+    pub operator_count: PodU64,
+    pub epoch_length: PodU64,
+    pub bump: u8,
+}
+
+/// View of a [`Ncn`](crate::accounts::Ncn).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct NcnRef {
+    pub discriminator: PodU64,
+    pub base: Pubkey,
+    pub admin: Pubkey,
+    pub operator_admin: Pubkey,
+    pub vault_admin: Pubkey,
+    pub slasher_admin: Pubkey,
+    pub delegate_admin: Pubkey,
+    pub metadata_admin: Pubkey,
+    pub weight_table_admin: Pubkey,
+    pub ncn_program_admin: Pubkey,
+    pub index: PodU64,
+    pub operator_count: PodU64,
+    pub vault_count: PodU64,
+    pub slasher_count: PodU64,
+    pub bump: u8,
+}
+
+/// View of a [`Operator`](crate::accounts::Operator).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct OperatorRef {
+    pub discriminator: PodU64,
+    pub base: Pubkey,
+    pub admin: Pubkey,
+    pub ncn_admin: Pubkey,
+    pub vault_admin: Pubkey,
+    pub delegate_admin: Pubkey,
+    pub metadata_admin: Pubkey,
+    pub voter: Pubkey,
+    pub index: PodU64,
+    pub ncn_count: PodU64,
+    pub vault_count: PodU64,
+    pub operator_fee_bps: PodU16,
+    pub bump: u8,
+}
+
+/// View of a [`NcnOperatorState`](crate::accounts::NcnOperatorState).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct NcnOperatorStateRef {
+    pub discriminator: PodU64,
+    pub ncn: Pubkey,
+    pub operator: Pubkey,
+    pub index: PodU64,
+    pub ncn_opt_in_state: SlotToggleRef,
+    pub operator_opt_in_state: SlotToggleRef,
+    pub bump: u8,
+}
+
+/// View of a [`OperatorVaultTicket`](crate::accounts::OperatorVaultTicket).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct OperatorVaultTicketRef {
+    pub discriminator: PodU64,
+    pub operator: Pubkey,
+    pub vault: Pubkey,
+    pub index: PodU64,
+    pub state: SlotToggleRef,
+    pub bump: u8,
+}
+
+/// View of a [`NcnVaultTicket`](crate::accounts::NcnVaultTicket).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct NcnVaultTicketRef {
+    pub discriminator: PodU64,
+    pub ncn: Pubkey,
+    pub vault: Pubkey,
+    pub index: PodU64,
+    pub state: SlotToggleRef,
+    pub bump: u8,
+}
+
+/// View of a [`NcnVaultSlasherTicket`](crate::accounts::NcnVaultSlasherTicket).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct NcnVaultSlasherTicketRef {
+    pub discriminator: PodU64,
+    pub ncn: Pubkey,
+    pub vault: Pubkey,
+    pub slasher: Pubkey,
+    pub max_slashable_per_epoch: PodU64,
+    pub index: PodU64,
+    pub state: SlotToggleRef,
+    pub bump: u8,
+}
+
+account_ref! {
+    ConfigRef => CONFIG_DISCRIMINATOR,
+    NcnRef => NCN_DISCRIMINATOR,
+    OperatorRef => OPERATOR_DISCRIMINATOR,
+    NcnOperatorStateRef => NCN_OPERATOR_STATE_DISCRIMINATOR,
+    OperatorVaultTicketRef => OPERATOR_VAULT_TICKET_DISCRIMINATOR,
+    NcnVaultTicketRef => NCN_VAULT_TICKET_DISCRIMINATOR,
+    NcnVaultSlasherTicketRef => NCN_VAULT_SLASHER_TICKET_DISCRIMINATOR,
+}
@@ -0,0 +1,60 @@
+use std::io::ErrorKind;
+
+use jito_restaking_client::accounts::{
+    JitoRestakingAccount, Operator, OperatorVaultTicket, OPERATOR_DISCRIMINATOR,
+    OPERATOR_VAULT_TICKET_DISCRIMINATOR,
+};
+use solana_program::pubkey::Pubkey;
+
+// zeroed account data with the discriminator and the first pubkey field set
+fn account_data(discriminator: u64, first_pubkey: &Pubkey) -> Vec<u8> {
+    let mut data = vec![0; 2048];
+    data[..8].copy_from_slice(&discriminator.to_le_bytes());
+    data[8..40].copy_from_slice(first_pubkey.as_ref());
+    data
+}
+
+#[test]
+fn dispatches_on_discriminator() {
+    let base = Pubkey::new_unique();
+
+    let account =
+        JitoRestakingAccount::try_from_bytes(&account_data(OPERATOR_DISCRIMINATOR, &base)).unwrap();
+    assert_eq!(account.discriminator(), OPERATOR_DISCRIMINATOR);
+    let operator = Operator::try_from(account).unwrap();
+    assert_eq!(operator.base, base);
+
+    // an OperatorVaultTicket has enough bytes to decode as an Operator
+    let account = JitoRestakingAccount::try_from_bytes(&account_data(
+        OPERATOR_VAULT_TICKET_DISCRIMINATOR,
+        &base,
+    ))
+    .unwrap();
+    let account = Operator::try_from(account).unwrap_err();
+    let ticket = OperatorVaultTicket::try_from(account).unwrap();
+    assert_eq!(ticket.operator, base);
+}
+
+#[test]
+fn rejects_unknown_discriminators() {
+    let data = account_data(42, &Pubkey::new_unique());
+    assert_eq!(
+        JitoRestakingAccount::try_from_bytes(&data)
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidData
+    );
+
+    assert_eq!(
+        JitoRestakingAccount::try_from_bytes(&[3, 0, 0])
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn rejects_truncated_accounts() {
+    let data = account_data(OPERATOR_DISCRIMINATOR, &Pubkey::new_unique());
+    assert!(JitoRestakingAccount::try_from_bytes(&data[..100]).is_err());
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "fetch")]
+
+use std::{
+    collections::HashMap,
+    io::{Error, ErrorKind},
+};
+
+use jito_restaking_client::{
+    accounts::{NCN_DISCRIMINATOR, OPERATOR_DISCRIMINATOR},
+    fetch::{
+        fetch_all_maybe_operator, fetch_ncn, fetch_operator, AccountFetcher, MaybeAccount,
+        RawAccount,
+    },
+    programs::JITO_RESTAKING_ID,
+};
+use solana_program::pubkey::Pubkey;
+
+// stands in for the RPC, serving accounts from memory
+#[derive(Default)]
+struct MockRpc {
+    accounts: HashMap<Pubkey, RawAccount>,
+}
+
+impl MockRpc {
+    fn insert(&mut self, address: Pubkey, owner: Pubkey, data: Vec<u8>) {
+        self.accounts.insert(
+            address,
+            RawAccount {
+                lamports: 1_000_000,
+                owner,
+                data,
+            },
+        );
+    }
+}
+
+impl AccountFetcher for MockRpc {
+    fn get_multiple_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<RawAccount>>, Error> {
+        Ok(addresses
+            .iter()
+            .map(|address| self.accounts.get(address).cloned())
+            .collect())
+    }
+}
+
+// zeroed account data with the discriminator and the first pubkey field set
+fn account_data(discriminator: u64, first_pubkey: &Pubkey) -> Vec<u8> {
+    let mut data = vec![0; 1024];
+    data[..8].copy_from_slice(&discriminator.to_le_bytes());
+    data[8..40].copy_from_slice(first_pubkey.as_ref());
+    data
+}
+
+#[test]
+fn fetches_operators_and_reports_missing_ones() {
+    let (operator, base, missing) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut rpc = MockRpc::default();
+    rpc.insert(
+        operator,
+        JITO_RESTAKING_ID,
+        account_data(OPERATOR_DISCRIMINATOR, &base),
+    );
+
+    let decoded = fetch_operator(&rpc, &operator).unwrap();
+    assert_eq!(decoded.data.base, base);
+
+    let accounts = fetch_all_maybe_operator(&rpc, &[operator, missing]).unwrap();
+    assert!(matches!(&accounts[0], MaybeAccount::Exists(account) if account.data.base == base));
+    assert_eq!(accounts[1], MaybeAccount::NotFound(missing));
+}
+
+#[test]
+fn rejects_other_account_types() {
+    let operator = Pubkey::new_unique();
+    let mut rpc = MockRpc::default();
+    rpc.insert(
+        operator,
+        JITO_RESTAKING_ID,
+        account_data(OPERATOR_DISCRIMINATOR, &Pubkey::new_unique()),
+    );
+
+    assert_eq!(
+        fetch_ncn(&rpc, &operator).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+
+    rpc.insert(
+        operator,
+        JITO_RESTAKING_ID,
+        account_data(NCN_DISCRIMINATOR, &Pubkey::new_unique()),
+    );
+    assert!(fetch_ncn(&rpc, &operator).is_ok());
+}
@@ -0,0 +1,53 @@
+use jito_restaking_client::pda;
+use solana_program::{pubkey, pubkey::Pubkey};
+
+const RESTAKING_PROGRAM: Pubkey = pubkey!("RestkWeAVL8fRGgzhfeoqFhsqKRchg6aa1XrcH96z4Q");
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+// the seeds as written by the `seeds` functions of the restaking program
+fn address(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &RESTAKING_PROGRAM)
+}
+
+#[test]
+fn config_is_the_deployed_one() {
+    assert_eq!(
+        pda::find_config_address().0,
+        pubkey!("4vvKh3Ws4vGzgXRVdo8SdL4jePXDvCqKVmi21BCBGwvn")
+    );
+}
+
+#[test]
+fn addresses_follow_the_program_seeds() {
+    let (ncn, operator, vault, slasher) = (key(1), key(2), key(3), key(4));
+
+    assert_eq!(pda::find_ncn_address(&key(5)), address(&[b"ncn", key(5).as_ref()]));
+    assert_eq!(
+        pda::find_operator_address(&key(5)),
+        address(&[b"operator", key(5).as_ref()])
+    );
+    assert_eq!(
+        pda::find_ncn_operator_state_address(&ncn, &operator),
+        address(&[b"ncn_operator_state", ncn.as_ref(), operator.as_ref()])
+    );
+    assert_eq!(
+        pda::find_ncn_vault_ticket_address(&ncn, &vault),
+        address(&[b"ncn_vault_ticket", ncn.as_ref(), vault.as_ref()])
+    );
+    assert_eq!(
+        pda::find_operator_vault_ticket_address(&operator, &vault),
+        address(&[b"operator_vault_ticket", operator.as_ref(), vault.as_ref()])
+    );
+    assert_eq!(
+        pda::find_ncn_vault_slasher_ticket_address(&ncn, &vault, &slasher),
+        address(&[
+            b"ncn_slasher_ticket",
+            ncn.as_ref(),
+            vault.as_ref(),
+            slasher.as_ref()
+        ])
+    );
+}
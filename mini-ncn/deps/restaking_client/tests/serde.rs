@@ -0,0 +1,187 @@
+#![cfg(feature = "serde")]
+
+use std::{fmt::Debug, fs, path::PathBuf};
+
+use jito_restaking_client::{
+    accounts::{
+        Config, Ncn, NcnOperatorState, NcnVaultSlasherTicket, NcnVaultTicket, Operator,
+        OperatorVaultTicket, CONFIG_DISCRIMINATOR, NCN_DISCRIMINATOR,
+        NCN_OPERATOR_STATE_DISCRIMINATOR, NCN_VAULT_SLASHER_TICKET_DISCRIMINATOR,
+        NCN_VAULT_TICKET_DISCRIMINATOR, OPERATOR_DISCRIMINATOR,
+        OPERATOR_VAULT_TICKET_DISCRIMINATOR,
+    },
+    types::SlotToggle,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use solana_program::pubkey::Pubkey;
+
+// compares against tests/snapshots/<name>.json, rewrite them with UPDATE_SNAPSHOTS=1
+fn assert_snapshot<T: Serialize + DeserializeOwned + PartialEq + Debug>(name: &str, value: &T) {
+    let full = serde_json::to_value(value).unwrap();
+    let json = serde_json::to_string_pretty(&full).unwrap() + "\n";
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{name}.json"));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, &json).unwrap();
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("missing {}, run with UPDATE_SNAPSHOTS=1", path.display()));
+    assert_eq!(json, expected, "{name} snapshot changed");
+    assert_eq!(&serde_json::from_value::<T>(full).unwrap(), value);
+}
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn slot_toggle() -> SlotToggle {
+    SlotToggle {
+        slot_added: 100,
+        slot_removed: 0,
+        reserved: [0; 32],
+    }
+}
+
+#[test]
+fn slot_toggle_json() {
+    assert_snapshot("slot_toggle", &slot_toggle());
+}
+
+#[test]
+fn config_json() {
+    assert_snapshot(
+        "config",
+        &Config {
+            discriminator: CONFIG_DISCRIMINATOR,
+            admin: key(1),
+            vault_program: key(2),
+            ncn_count: 1,
+            operator_count: 2,
+            epoch_length: 432_000,
+            bump: 255,
+            reserved: [0; 263],
+        },
+    );
+}
+
+#[test]
+fn ncn_json() {
+    assert_snapshot(
+        "ncn",
+        &Ncn {
+            discriminator: NCN_DISCRIMINATOR,
+            base: key(1),
+            admin: key(2),
+            operator_admin: key(2),
+            vault_admin: key(2),
+            slasher_admin: key(2),
+            delegate_admin: key(2),
+            metadata_admin: key(2),
+            weight_table_admin: key(2),
+            ncn_program_admin: key(3),
+            index: 0,
+            operator_count: 2,
+            vault_count: 1,
+            slasher_count: 0,
+            bump: 254,
+            reserved: [0; 263],
+        },
+    );
+}
+
+#[test]
+fn operator_json() {
+    assert_snapshot(
+        "operator",
+        &Operator {
+            discriminator: OPERATOR_DISCRIMINATOR,
+            base: key(1),
+            admin: key(2),
+            ncn_admin: key(2),
+            vault_admin: key(2),
+            delegate_admin: key(2),
+            metadata_admin: key(2),
+            voter: key(3),
+            index: 1,
+            ncn_count: 1,
+            vault_count: 1,
+            operator_fee_bps: 1_000,
+            bump: 253,
+            reserved_space: [0; 261],
+        },
+    );
+}
+
+#[test]
+fn ncn_operator_state_json() {
+    assert_snapshot(
+        "ncn_operator_state",
+        &NcnOperatorState {
+            discriminator: NCN_OPERATOR_STATE_DISCRIMINATOR,
+            ncn: key(1),
+            operator: key(2),
+            index: 0,
+            ncn_opt_in_state: slot_toggle(),
+            operator_opt_in_state: SlotToggle {
+                slot_added: 101,
+                slot_removed: 0,
+                reserved: [0; 32],
+            },
+            bump: 252,
+            reserved: [0; 263],
+        },
+    );
+}
+
+#[test]
+fn operator_vault_ticket_json() {
+    assert_snapshot(
+        "operator_vault_ticket",
+        &OperatorVaultTicket {
+            discriminator: OPERATOR_VAULT_TICKET_DISCRIMINATOR,
+            operator: key(1),
+            vault: key(2),
+            index: 0,
+            state: slot_toggle(),
+            bump: 251,
+            reserved: [0; 263],
+        },
+    );
+}
+
+#[test]
+fn ncn_vault_ticket_json() {
+    assert_snapshot(
+        "ncn_vault_ticket",
+        &NcnVaultTicket {
+            discriminator: NCN_VAULT_TICKET_DISCRIMINATOR,
+            ncn: key(1),
+            vault: key(2),
+            index: 0,
+            state: slot_toggle(),
+            bump: 250,
+            reserved: [0; 263],
+        },
+    );
+}
+
+#[test]
+fn ncn_vault_slasher_ticket_json() {
+    assert_snapshot(
+        "ncn_vault_slasher_ticket",
+        &NcnVaultSlasherTicket {
+            discriminator: NCN_VAULT_SLASHER_TICKET_DISCRIMINATOR,
+            ncn: key(1),
+            vault: key(2),
+            slasher: key(3),
+            max_slashable_per_epoch: 10_000,
+            index: 0,
+            state: slot_toggle(),
+            bump: 249,
+            reserved: [0; 263],
+        },
+    );
+}
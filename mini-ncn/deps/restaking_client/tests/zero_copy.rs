@@ -0,0 +1,125 @@
+use std::io::ErrorKind;
+
+use borsh::BorshSerialize;
+use jito_restaking_client::{
+    accounts::{
+        NcnOperatorState, Operator, OperatorVaultTicket, NCN_OPERATOR_STATE_DISCRIMINATOR,
+        OPERATOR_DISCRIMINATOR, OPERATOR_VAULT_TICKET_DISCRIMINATOR,
+    },
+    types::SlotToggle,
+    zero_copy::{AccountRef, NcnOperatorStateRef, OperatorRef, OperatorVaultTicketRef},
+};
+use solana_program::pubkey::Pubkey;
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn slot_toggle(slot_added: u64) -> SlotToggle {
+    SlotToggle {
+        slot_added,
+        slot_removed: 0,
+        reserved: [0; 32],
+    }
+}
+
+// borsh layout, offset by one byte to show views need no alignment
+fn account_data(account: &impl BorshSerialize) -> Vec<u8> {
+    let mut data = vec![0xff];
+    account.serialize(&mut data).unwrap();
+    data
+}
+
+#[test]
+fn operator_view_matches_borsh_layout() {
+    let operator = Operator {
+        discriminator: OPERATOR_DISCRIMINATOR,
+        base: key(1),
+        admin: key(2),
+        ncn_admin: key(3),
+        vault_admin: key(4),
+        delegate_admin: key(5),
+        metadata_admin: key(6),
+        voter: key(7),
+        index: 8,
+        ncn_count: 9,
+        vault_count: 10,
+        operator_fee_bps: 11,
+        bump: 12,
+        reserved_space: [0; 261],
+    };
+    let data = account_data(&operator);
+    let view = OperatorRef::try_from_bytes(&data[1..]).unwrap();
+    assert_eq!(view.admin, operator.admin);
+    assert_eq!(view.voter, operator.voter);
+    assert_eq!(u64::from(view.vault_count), operator.vault_count);
+    assert_eq!(u16::from(view.operator_fee_bps), operator.operator_fee_bps);
+    assert_eq!(view.bump, operator.bump);
+}
+
+#[test]
+fn ticket_views_match_borsh_layout() {
+    let state = NcnOperatorState {
+        discriminator: NCN_OPERATOR_STATE_DISCRIMINATOR,
+        ncn: key(1),
+        operator: key(2),
+        index: 3,
+        ncn_opt_in_state: slot_toggle(4),
+        operator_opt_in_state: slot_toggle(5),
+        bump: 6,
+        reserved: [0; 263],
+    };
+    let data = account_data(&state);
+    let view = NcnOperatorStateRef::try_from_bytes(&data[1..]).unwrap();
+    assert_eq!(u64::from(view.ncn_opt_in_state.slot_added), 4);
+    assert_eq!(u64::from(view.operator_opt_in_state.slot_added), 5);
+    assert_eq!(view.bump, state.bump);
+
+    let ticket = OperatorVaultTicket {
+        discriminator: OPERATOR_VAULT_TICKET_DISCRIMINATOR,
+        operator: key(1),
+        vault: key(2),
+        index: 3,
+        state: slot_toggle(100),
+        bump: 4,
+        reserved: [0; 263],
+    };
+    let data = account_data(&ticket);
+    let view = OperatorVaultTicketRef::try_from_bytes(&data[1..]).unwrap();
+    assert_eq!(view.operator, ticket.operator);
+    assert_eq!(view.bump, ticket.bump);
+
+    // the view computes the same toggle state as the decoded account
+    for slot in [100, 150, 200, 250] {
+        assert_eq!(
+            view.state.state(slot, 100).unwrap(),
+            ticket.state.state(slot, 100).unwrap()
+        );
+    }
+}
+
+#[test]
+fn rejects_other_account_types() {
+    let ticket = OperatorVaultTicket {
+        discriminator: OPERATOR_VAULT_TICKET_DISCRIMINATOR,
+        operator: key(1),
+        vault: key(2),
+        index: 3,
+        state: slot_toggle(100),
+        bump: 4,
+        reserved: [0; 263],
+    };
+    let data = account_data(&ticket);
+
+    // an OperatorVaultTicket has enough bytes to view as an Operator
+    assert_eq!(
+        OperatorRef::try_from_bytes(&data[1..]).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    assert_eq!(
+        OperatorVaultTicketRef::try_from_bytes(&data[1..50])
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidData
+    );
+}
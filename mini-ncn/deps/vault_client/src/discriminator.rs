@@ -0,0 +1,112 @@
+use std::io::{Error, ErrorKind};
+
+use crate::accounts::{
+    Config, Vault, VaultNcnSlasherOperatorTicket, VaultNcnSlasherTicket, VaultNcnTicket,
+    VaultOperatorDelegation, VaultStakerWithdrawalTicket, VaultUpdateStateTracker,
+};
+
+/// Value of the leading `discriminator: u64` of a vault program account.
+pub trait AccountDiscriminator {
+    const DISCRIMINATOR: u64;
+}
+
+pub const CONFIG_DISCRIMINATOR: u64 = 1;
+pub const VAULT_DISCRIMINATOR: u64 = 2;
+pub const VAULT_NCN_TICKET_DISCRIMINATOR: u64 = 3;
+pub const VAULT_OPERATOR_DELEGATION_DISCRIMINATOR: u64 = 4;
+pub const VAULT_NCN_SLASHER_TICKET_DISCRIMINATOR: u64 = 5;
+pub const VAULT_NCN_SLASHER_OPERATOR_TICKET_DISCRIMINATOR: u64 = 6;
+pub const VAULT_STAKER_WITHDRAWAL_TICKET_DISCRIMINATOR: u64 = 7;
+pub const VAULT_UPDATE_STATE_TRACKER_DISCRIMINATOR: u64 = 8;
+
+impl AccountDiscriminator for Config {
+    const DISCRIMINATOR: u64 = CONFIG_DISCRIMINATOR;
+}
+
+impl AccountDiscriminator for Vault {
+    const DISCRIMINATOR: u64 = VAULT_DISCRIMINATOR;
+}
+
+impl AccountDiscriminator for VaultNcnTicket {
+    const DISCRIMINATOR: u64 = VAULT_NCN_TICKET_DISCRIMINATOR;
+}
+
+impl AccountDiscriminator for VaultOperatorDelegation {
+    const DISCRIMINATOR: u64 = VAULT_OPERATOR_DELEGATION_DISCRIMINATOR;
+}
+
+impl AccountDiscriminator for VaultNcnSlasherTicket {
+    const DISCRIMINATOR: u64 = VAULT_NCN_SLASHER_TICKET_DISCRIMINATOR;
+}
+
+impl AccountDiscriminator for VaultNcnSlasherOperatorTicket {
+    const DISCRIMINATOR: u64 = VAULT_NCN_SLASHER_OPERATOR_TICKET_DISCRIMINATOR;
+}
+
+impl AccountDiscriminator for VaultStakerWithdrawalTicket {
+    const DISCRIMINATOR: u64 = VAULT_STAKER_WITHDRAWAL_TICKET_DISCRIMINATOR;
+}
+
+impl AccountDiscriminator for VaultUpdateStateTracker {
+    const DISCRIMINATOR: u64 = VAULT_UPDATE_STATE_TRACKER_DISCRIMINATOR;
+}
+
+/// Reads the leading discriminator, `None` if the data is too short to hold one.
+pub fn account_discriminator(data: &[u8]) -> Option<u64> {
+    data.get(..8)
+        .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+macro_rules! account_enum {
+    ($($account:ident => $discriminator:ident,)*) => {
+        /// Any vault program account, decoded according to its discriminator.
+        #[derive(Clone, Debug, Eq, PartialEq)]
+        pub enum JitoVaultAccount {
+            $($account($account),)*
+        }
+
+        impl JitoVaultAccount {
+            /// Decodes `data` as the account type its discriminator names.
+            /// Unlike the generated `from_bytes`, fails on unknown discriminators.
+            pub fn try_from_bytes(data: &[u8]) -> Result<Self, Error> {
+                match account_discriminator(data) {
+                    $(Some($discriminator) => $account::from_bytes(data).map(Self::$account),)*
+                    discriminator => Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!("unknown vault account discriminator {discriminator:?}"),
+                    )),
+                }
+            }
+
+            pub fn discriminator(&self) -> u64 {
+                match self {
+                    $(Self::$account(_) => $discriminator,)*
+                }
+            }
+        }
+
+        $(
+            impl TryFrom<JitoVaultAccount> for $account {
+                type Error = JitoVaultAccount;
+
+                fn try_from(account: JitoVaultAccount) -> Result<Self, Self::Error> {
+                    match account {
+                        JitoVaultAccount::$account(account) => Ok(account),
+                        account => Err(account),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+account_enum! {
+    Config => CONFIG_DISCRIMINATOR,
+    Vault => VAULT_DISCRIMINATOR,
+    VaultNcnTicket => VAULT_NCN_TICKET_DISCRIMINATOR,
+    VaultOperatorDelegation => VAULT_OPERATOR_DELEGATION_DISCRIMINATOR,
+    VaultNcnSlasherTicket => VAULT_NCN_SLASHER_TICKET_DISCRIMINATOR,
+    VaultNcnSlasherOperatorTicket => VAULT_NCN_SLASHER_OPERATOR_TICKET_DISCRIMINATOR,
+    VaultStakerWithdrawalTicket => VAULT_STAKER_WITHDRAWAL_TICKET_DISCRIMINATOR,
+    VaultUpdateStateTracker => VAULT_UPDATE_STATE_TRACKER_DISCRIMINATOR,
+}
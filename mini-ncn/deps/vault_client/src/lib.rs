@@ -1,14 +1,24 @@
+//! Client of the Jito vault program.
+//!
+//! The `fetch` feature adds the `fetch` module, which fetches and decodes
+//! accounts through a `fetch::AccountFetcher`, implemented for `RpcClient`.
 #![allow(clippy::all)]
 #![allow(clippy::nursery)]
 #![allow(clippy::integer_division)]
 #![allow(clippy::arithmetic_side_effects)]
 #![allow(clippy::style)]
 #![allow(clippy::perf)]
+mod discriminator;
+#[cfg(feature = "fetch")]
+pub mod fetch;
 mod generated;
+pub mod pda;
+pub mod zero_copy;
 
 use generated::*;
 
 pub mod accounts {
+    pub use super::discriminator::*;
     pub use super::generated::accounts::*;
 }
 
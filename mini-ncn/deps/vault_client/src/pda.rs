@@ -0,0 +1,104 @@
+//! Seeds and address derivation of the vault program accounts.
+
+use solana_program::pubkey::Pubkey;
+
+use crate::programs::JITO_VAULT_ID;
+
+pub const CONFIG_SEED: &[u8] = b"config";
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const BURN_VAULT_SEED: &[u8] = b"burn_vault";
+pub const VAULT_NCN_TICKET_SEED: &[u8] = b"vault_ncn_ticket";
+pub const VAULT_OPERATOR_DELEGATION_SEED: &[u8] = b"vault_operator_delegation";
+pub const VAULT_NCN_SLASHER_TICKET_SEED: &[u8] = b"vault_slasher_ticket";
+pub const VAULT_NCN_SLASHER_OPERATOR_TICKET_SEED: &[u8] = b"vault_ncn_slasher_operator";
+pub const VAULT_STAKER_WITHDRAWAL_TICKET_SEED: &[u8] = b"vault_staker_withdrawal_ticket";
+pub const VAULT_UPDATE_STATE_TRACKER_SEED: &[u8] = b"vault_update_state_tracker";
+
+pub fn find_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &JITO_VAULT_ID)
+}
+
+pub fn find_vault_address(base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, base.as_ref()], &JITO_VAULT_ID)
+}
+
+/// Holds the VRT minted on vault initialization, `base` is the one of the vault
+pub fn find_burn_vault_address(base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BURN_VAULT_SEED, base.as_ref()], &JITO_VAULT_ID)
+}
+
+pub fn find_vault_ncn_ticket_address(vault: &Pubkey, ncn: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VAULT_NCN_TICKET_SEED, vault.as_ref(), ncn.as_ref()],
+        &JITO_VAULT_ID,
+    )
+}
+
+pub fn find_vault_operator_delegation_address(vault: &Pubkey, operator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VAULT_OPERATOR_DELEGATION_SEED,
+            vault.as_ref(),
+            operator.as_ref(),
+        ],
+        &JITO_VAULT_ID,
+    )
+}
+
+pub fn find_vault_ncn_slasher_ticket_address(
+    vault: &Pubkey,
+    ncn: &Pubkey,
+    slasher: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VAULT_NCN_SLASHER_TICKET_SEED,
+            vault.as_ref(),
+            ncn.as_ref(),
+            slasher.as_ref(),
+        ],
+        &JITO_VAULT_ID,
+    )
+}
+
+pub fn find_vault_ncn_slasher_operator_ticket_address(
+    vault: &Pubkey,
+    ncn: &Pubkey,
+    slasher: &Pubkey,
+    operator: &Pubkey,
+    epoch: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VAULT_NCN_SLASHER_OPERATOR_TICKET_SEED,
+            vault.as_ref(),
+            ncn.as_ref(),
+            slasher.as_ref(),
+            operator.as_ref(),
+            &epoch.to_le_bytes(),
+        ],
+        &JITO_VAULT_ID,
+    )
+}
+
+pub fn find_vault_staker_withdrawal_ticket_address(vault: &Pubkey, base: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VAULT_STAKER_WITHDRAWAL_TICKET_SEED,
+            vault.as_ref(),
+            base.as_ref(),
+        ],
+        &JITO_VAULT_ID,
+    )
+}
+
+pub fn find_vault_update_state_tracker_address(vault: &Pubkey, ncn_epoch: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            VAULT_UPDATE_STATE_TRACKER_SEED,
+            vault.as_ref(),
+            &ncn_epoch.to_le_bytes(),
+        ],
+        &JITO_VAULT_ID,
+    )
+}
@@ -0,0 +1,255 @@
+//! Zero-copy views of vault program accounts.
+//!
+//! Each `*Ref` mirrors the Borsh layout of its account up to the reserved
+//! bytes. Its fields only hold byte arrays, so it can be borrowed straight from
+//! account data at any alignment instead of deserializing a copy.
+
+use std::{
+    io::{Error, ErrorKind},
+    mem::size_of,
+};
+
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+
+use crate::accounts::{
+    account_discriminator, AccountDiscriminator, CONFIG_DISCRIMINATOR, VAULT_DISCRIMINATOR,
+    VAULT_NCN_SLASHER_OPERATOR_TICKET_DISCRIMINATOR, VAULT_NCN_SLASHER_TICKET_DISCRIMINATOR,
+    VAULT_NCN_TICKET_DISCRIMINATOR, VAULT_OPERATOR_DELEGATION_DISCRIMINATOR,
+    VAULT_STAKER_WITHDRAWAL_TICKET_DISCRIMINATOR, VAULT_UPDATE_STATE_TRACKER_DISCRIMINATOR,
+};
+
+/// Little-endian `u64` without alignment.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Pod, Zeroable)]
+pub struct PodU64(pub [u8; 8]);
+
+impl From<PodU64> for u64 {
+    fn from(value: PodU64) -> Self {
+        u64::from_le_bytes(value.0)
+    }
+}
+
+/// Little-endian `u16` without alignment.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Pod, Zeroable)]
+pub struct PodU16(pub [u8; 2]);
+
+impl From<PodU16> for u16 {
+    fn from(value: PodU16) -> Self {
+        u16::from_le_bytes(value.0)
+    }
+}
+
+/// Borsh `bool`, any non-zero byte reads as `true`.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Pod, Zeroable)]
+pub struct PodBool(pub u8);
+
+impl From<PodBool> for bool {
+    fn from(value: PodBool) -> Self {
+        value.0 != 0
+    }
+}
+
+/// A view that can be borrowed from the data of its account.
+pub trait AccountRef: AccountDiscriminator + Pod {
+    /// Borrows the view from `data`, checking the discriminator and length.
+    fn try_from_bytes(data: &[u8]) -> Result<&Self, Error> {
+        let discriminator = account_discriminator(data);
+        if discriminator != Some(Self::DISCRIMINATOR) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "account has discriminator {discriminator:?}, expected {}",
+                    Self::DISCRIMINATOR
+                ),
+            ));
+        }
+
+        data.get(..size_of::<Self>())
+            .and_then(|data| bytemuck::try_from_bytes(data).ok())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "account data is too short"))
+    }
+}
+
+macro_rules! account_ref {
+    ($($account:ident => $discriminator:ident,)*) => {
+        $(
+            impl AccountDiscriminator for $account {
+                const DISCRIMINATOR: u64 = $discriminator;
+            }
+
+            impl AccountRef for $account {}
+        )*
+    };
+}
+
+/// View of a [`SlotToggle`](crate::types::SlotToggle).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct SlotToggleRef {
+    pub slot_added: PodU64,
+    pub slot_removed: PodU64,
+    pub reserved: [u8; 32],
+}
+
+/// View of a [`DelegationState`](crate::types::DelegationState).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct DelegationStateRef {
+    pub staked_amount: PodU64,
+    pub enqueued_for_cooldown_amount: PodU64,
+    pub cooling_down_amount: PodU64,
+    pub reserved: [u8; 256],
+}
+
+/// View of a [`Config`](crate::accounts::Config).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct ConfigRef {
+    pub discriminator: PodU64,
+    pub admin: Pubkey,
+    pub restaking_program: Pubkey,
+    pub epoch_length: PodU64,
+    pub num_vaults: PodU64,
+    pub deposit_withdrawal_fee_cap_bps: PodU16,
+    pub fee_rate_of_change_bps: PodU16,
+    pub fee_bump_bps: PodU16,
+    pub program_fee_bps: PodU16,
+    pub program_fee_wallet: Pubkey,
+    pub fee_admin: Pubkey,
+    pub bump: u8,
+}
+
+/// View of a [`Vault`](crate::accounts::Vault).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct VaultRef {
+    pub discriminator: PodU64,
+    pub base: Pubkey,
+    pub vrt_mint: Pubkey,
+    pub supported_mint: Pubkey,
+    pub vrt_supply: PodU64,
+    pub tokens_deposited: PodU64,
+    pub deposit_capacity: PodU64,
+    pub delegation_state: DelegationStateRef,
+    pub additional_assets_need_unstaking: PodU64,
+    pub vrt_enqueued_for_cooldown_amount: PodU64,
+    pub vrt_cooling_down_amount: PodU64,
+    pub vrt_ready_to_claim_amount: PodU64,
+    pub admin: Pubkey,
+    pub delegation_admin: Pubkey,
+    pub operator_admin: Pubkey,
+    pub ncn_admin: Pubkey,
+    pub slasher_admin: Pubkey,
+    pub capacity_admin: Pubkey,
+    pub fee_admin: Pubkey,
+    pub delegate_asset_admin: Pubkey,
+    pub fee_wallet: Pubkey,
+    pub mint_burn_admin: Pubkey,
+    pub metadata_admin: Pubkey,
+    pub vault_index: PodU64,
+    pub ncn_count: PodU64,
+    pub operator_count: PodU64,
+    pub slasher_count: PodU64,
+    pub last_fee_change_slot: PodU64,
+    pub last_full_state_update_slot: PodU64,
+    pub deposit_fee_bps: PodU16,
+    pub withdrawal_fee_bps: PodU16,
+    pub next_withdrawal_fee_bps: PodU16,
+    pub reward_fee_bps: PodU16,
+    pub program_fee_bps: PodU16,
+    pub bump: u8,
+    pub is_paused: PodBool,
+    pub last_start_state_update_slot: PodU64,
+}
+
+/// View of a [`VaultNcnTicket`](crate::accounts::VaultNcnTicket).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct VaultNcnTicketRef {
+    pub discriminator: PodU64,
+    pub vault: Pubkey,
+    pub ncn: Pubkey,
+    pub index: PodU64,
+    pub state: SlotToggleRef,
+    pub bump: u8,
+}
+
+/// View of a [`VaultOperatorDelegation`](crate::accounts::VaultOperatorDelegation).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct VaultOperatorDelegationRef {
+    pub discriminator: PodU64,
+    pub vault: Pubkey,
+    pub operator: Pubkey,
+    pub delegation_state: DelegationStateRef,
+    pub last_update_slot: PodU64,
+    pub index: PodU64,
+    pub bump: u8,
+}
+
+/// View of a [`VaultNcnSlasherTicket`](crate::accounts::VaultNcnSlasherTicket).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct VaultNcnSlasherTicketRef {
+    pub discriminator: PodU64,
+    pub vault: Pubkey,
+    pub ncn: Pubkey,
+    pub slasher: Pubkey,
+    pub max_slashable_per_epoch: PodU64,
+    pub index: PodU64,
+    pub state: SlotToggleRef,
+    pub bump: u8,
+}
+
+/// View of a [`VaultNcnSlasherOperatorTicket`](crate::accounts::VaultNcnSlasherOperatorTicket).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct VaultNcnSlasherOperatorTicketRef {
+    pub discriminator: PodU64,
+    pub vault: Pubkey,
+    pub ncn: Pubkey,
+    pub slasher: Pubkey,
+    pub operator: Pubkey,
+    pub epoch: PodU64,
+    pub slashed: PodU64,
+    pub bump: u8,
+}
+
+/// View of a [`VaultStakerWithdrawalTicket`](crate::accounts::VaultStakerWithdrawalTicket).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct VaultStakerWithdrawalTicketRef {
+    pub discriminator: PodU64,
+    pub vault: Pubkey,
+    pub staker: Pubkey,
+    pub base: Pubkey,
+    pub vrt_amount: PodU64,
+    pub slot_unstaked: PodU64,
+    pub bump: u8,
+}
+
+/// View of a [`VaultUpdateStateTracker`](crate::accounts::VaultUpdateStateTracker).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Pod, Zeroable)]
+pub struct VaultUpdateStateTrackerRef {
+    pub discriminator: PodU64,
+    pub vault: Pubkey,
+    pub ncn_epoch: PodU64,
+    pub last_updated_index: PodU64,
+    pub delegation_state: DelegationStateRef,
+    pub withdrawal_allocation_method: u8,
+}
+
+account_ref! {
+    ConfigRef => CONFIG_DISCRIMINATOR,
+    VaultRef => VAULT_DISCRIMINATOR,
+    VaultNcnTicketRef => VAULT_NCN_TICKET_DISCRIMINATOR,
+    VaultOperatorDelegationRef => VAULT_OPERATOR_DELEGATION_DISCRIMINATOR,
+    VaultNcnSlasherTicketRef => VAULT_NCN_SLASHER_TICKET_DISCRIMINATOR,
+    VaultNcnSlasherOperatorTicketRef => VAULT_NCN_SLASHER_OPERATOR_TICKET_DISCRIMINATOR,
+    VaultStakerWithdrawalTicketRef => VAULT_STAKER_WITHDRAWAL_TICKET_DISCRIMINATOR,
+    VaultUpdateStateTrackerRef => VAULT_UPDATE_STATE_TRACKER_DISCRIMINATOR,
+}
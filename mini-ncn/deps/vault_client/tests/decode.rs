@@ -0,0 +1,62 @@
+use std::io::ErrorKind;
+
+use jito_vault_client::accounts::{
+    JitoVaultAccount, Vault, VaultOperatorDelegation, VAULT_DISCRIMINATOR,
+    VAULT_OPERATOR_DELEGATION_DISCRIMINATOR,
+};
+use solana_program::pubkey::Pubkey;
+
+// zeroed account data with the discriminator and the first pubkey field set
+fn account_data(discriminator: u64, first_pubkey: &Pubkey) -> Vec<u8> {
+    let mut data = vec![0; 2048];
+    data[..8].copy_from_slice(&discriminator.to_le_bytes());
+    data[8..40].copy_from_slice(first_pubkey.as_ref());
+    data
+}
+
+#[test]
+fn dispatches_on_discriminator() {
+    let base = Pubkey::new_unique();
+
+    let account =
+        JitoVaultAccount::try_from_bytes(&account_data(VAULT_DISCRIMINATOR, &base)).unwrap();
+    assert_eq!(account.discriminator(), VAULT_DISCRIMINATOR);
+    let vault = Vault::try_from(account).unwrap();
+    assert_eq!(vault.base, base);
+
+    // a VaultOperatorDelegation has enough bytes to decode as a Vault
+    let account = JitoVaultAccount::try_from_bytes(&account_data(
+        VAULT_OPERATOR_DELEGATION_DISCRIMINATOR,
+        &base,
+    ))
+    .unwrap();
+    assert!(matches!(
+        account,
+        JitoVaultAccount::VaultOperatorDelegation(_)
+    ));
+    let account = Vault::try_from(account).unwrap_err();
+    let delegation = VaultOperatorDelegation::try_from(account).unwrap();
+    assert_eq!(delegation.vault, base);
+}
+
+#[test]
+fn rejects_unknown_discriminators() {
+    let data = account_data(42, &Pubkey::new_unique());
+    assert_eq!(
+        JitoVaultAccount::try_from_bytes(&data).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+
+    assert_eq!(
+        JitoVaultAccount::try_from_bytes(&[2, 0, 0])
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn rejects_truncated_accounts() {
+    let data = account_data(VAULT_DISCRIMINATOR, &Pubkey::new_unique());
+    assert!(JitoVaultAccount::try_from_bytes(&data[..100]).is_err());
+}
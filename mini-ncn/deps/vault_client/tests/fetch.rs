@@ -0,0 +1,164 @@
+#![cfg(feature = "fetch")]
+
+use std::{
+    collections::HashMap,
+    io::{Error, ErrorKind},
+};
+
+use jito_vault_client::{
+    accounts::{
+        VaultOperatorDelegation, VAULT_DISCRIMINATOR, VAULT_OPERATOR_DELEGATION_DISCRIMINATOR,
+    },
+    fetch::{
+        fetch_all_maybe_vault, fetch_all_vault, fetch_maybe_vault, fetch_vault,
+        fetch_vault_operator_delegation, AccountFetcher, MaybeAccount, RawAccount,
+    },
+    programs::JITO_VAULT_ID,
+};
+use solana_program::pubkey::Pubkey;
+
+// stands in for the RPC, serving accounts from memory
+#[derive(Default)]
+struct MockRpc {
+    accounts: HashMap<Pubkey, RawAccount>,
+}
+
+impl MockRpc {
+    fn insert(&mut self, address: Pubkey, owner: Pubkey, data: Vec<u8>) {
+        self.accounts.insert(
+            address,
+            RawAccount {
+                lamports: 1_000_000,
+                owner,
+                data,
+            },
+        );
+    }
+}
+
+impl AccountFetcher for MockRpc {
+    fn get_multiple_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<Option<RawAccount>>, Error> {
+        Ok(addresses
+            .iter()
+            .map(|address| self.accounts.get(address).cloned())
+            .collect())
+    }
+}
+
+// zeroed account data with the discriminator and the first pubkey field set
+fn account_data(discriminator: u64, first_pubkey: &Pubkey) -> Vec<u8> {
+    let mut data = vec![0; 2048];
+    data[..8].copy_from_slice(&discriminator.to_le_bytes());
+    data[8..40].copy_from_slice(first_pubkey.as_ref());
+    data
+}
+
+#[test]
+fn fetches_and_decodes_accounts() {
+    let (vault, base) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut rpc = MockRpc::default();
+    rpc.insert(
+        vault,
+        JITO_VAULT_ID,
+        account_data(VAULT_DISCRIMINATOR, &base),
+    );
+
+    let decoded = fetch_vault(&rpc, &vault).unwrap();
+    assert_eq!(decoded.address, vault);
+    assert_eq!(decoded.account.owner, JITO_VAULT_ID);
+    assert_eq!(decoded.data.discriminator, VAULT_DISCRIMINATOR);
+    assert_eq!(decoded.data.base, base);
+}
+
+#[test]
+fn missing_accounts_are_not_found() {
+    let (vault, missing) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let mut rpc = MockRpc::default();
+    rpc.insert(
+        vault,
+        JITO_VAULT_ID,
+        account_data(VAULT_DISCRIMINATOR, &Pubkey::new_unique()),
+    );
+
+    let accounts = fetch_all_maybe_vault(&rpc, &[missing, vault]).unwrap();
+    assert_eq!(accounts[0], MaybeAccount::NotFound(missing));
+    assert!(matches!(&accounts[1], MaybeAccount::Exists(account) if account.address == vault));
+
+    assert_eq!(
+        fetch_maybe_vault(&rpc, &missing).unwrap(),
+        MaybeAccount::NotFound(missing)
+    );
+    assert_eq!(
+        fetch_vault(&rpc, &missing).unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+    assert_eq!(
+        fetch_all_vault(&rpc, &[vault, missing]).unwrap_err().kind(),
+        ErrorKind::NotFound
+    );
+}
+
+#[test]
+fn rejects_other_account_types() {
+    let delegation = Pubkey::new_unique();
+    let mut rpc = MockRpc::default();
+    rpc.insert(
+        delegation,
+        JITO_VAULT_ID,
+        account_data(
+            VAULT_OPERATOR_DELEGATION_DISCRIMINATOR,
+            &Pubkey::new_unique(),
+        ),
+    );
+
+    // a VaultOperatorDelegation has enough bytes to decode as a Vault
+    assert_eq!(
+        fetch_vault(&rpc, &delegation).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    let decoded: VaultOperatorDelegation = fetch_vault_operator_delegation(&rpc, &delegation)
+        .unwrap()
+        .data;
+    assert_eq!(
+        decoded.discriminator,
+        VAULT_OPERATOR_DELEGATION_DISCRIMINATOR
+    );
+}
+
+#[test]
+fn rejects_accounts_of_other_programs() {
+    let vault = Pubkey::new_unique();
+    let mut rpc = MockRpc::default();
+    rpc.insert(
+        vault,
+        Pubkey::new_unique(),
+        account_data(VAULT_DISCRIMINATOR, &Pubkey::new_unique()),
+    );
+
+    assert_eq!(
+        fetch_vault(&rpc, &vault).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+}
+
+#[test]
+fn rejects_truncated_accounts() {
+    let vault = Pubkey::new_unique();
+    let mut rpc = MockRpc::default();
+    rpc.insert(
+        vault,
+        JITO_VAULT_ID,
+        account_data(VAULT_DISCRIMINATOR, &Pubkey::new_unique())[..100].to_vec(),
+    );
+
+    assert!(fetch_vault(&rpc, &vault).is_err());
+
+    rpc.insert(vault, JITO_VAULT_ID, vec![2, 0, 0]);
+    assert_eq!(
+        fetch_vault(&rpc, &vault).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+}
@@ -0,0 +1,71 @@
+use jito_vault_client::pda;
+use solana_program::{pubkey, pubkey::Pubkey};
+
+const VAULT_PROGRAM: Pubkey = pubkey!("Vau1t6sLNxnzB7ZDsef8TLbPLfyZMYXH8WTNqUdm9g8");
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+// the seeds as written by the `seeds` functions of the vault program
+fn address(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &VAULT_PROGRAM)
+}
+
+#[test]
+fn config_is_the_deployed_one() {
+    assert_eq!(
+        pda::find_config_address().0,
+        pubkey!("UwuSgAq4zByffCGCrWH87DsjfsewYjuqHfJEpzw1Jq3")
+    );
+}
+
+#[test]
+fn addresses_follow_the_program_seeds() {
+    let (base, vault, ncn, operator, slasher) = (key(1), key(2), key(3), key(4), key(5));
+    let epoch = 640_u64;
+
+    assert_eq!(pda::find_vault_address(&base), address(&[b"vault", base.as_ref()]));
+    assert_eq!(
+        pda::find_burn_vault_address(&base),
+        address(&[b"burn_vault", base.as_ref()])
+    );
+    assert_eq!(
+        pda::find_vault_ncn_ticket_address(&vault, &ncn),
+        address(&[b"vault_ncn_ticket", vault.as_ref(), ncn.as_ref()])
+    );
+    assert_eq!(
+        pda::find_vault_operator_delegation_address(&vault, &operator),
+        address(&[b"vault_operator_delegation", vault.as_ref(), operator.as_ref()])
+    );
+    assert_eq!(
+        pda::find_vault_ncn_slasher_ticket_address(&vault, &ncn, &slasher),
+        address(&[
+            b"vault_slasher_ticket",
+            vault.as_ref(),
+            ncn.as_ref(),
+            slasher.as_ref()
+        ])
+    );
+    assert_eq!(
+        pda::find_vault_ncn_slasher_operator_ticket_address(
+            &vault, &ncn, &slasher, &operator, epoch
+        ),
+        address(&[
+            b"vault_ncn_slasher_operator",
+            vault.as_ref(),
+            ncn.as_ref(),
+            slasher.as_ref(),
+            operator.as_ref(),
+            &epoch.to_le_bytes()
+        ])
+    );
+    assert_eq!(
+        pda::find_vault_staker_withdrawal_ticket_address(&vault, &base),
+        address(&[b"vault_staker_withdrawal_ticket", vault.as_ref(), base.as_ref()])
+    );
+    assert_eq!(
+        pda::find_vault_update_state_tracker_address(&vault, epoch),
+        address(&[b"vault_update_state_tracker", vault.as_ref(), &epoch.to_le_bytes()])
+    );
+}
@@ -0,0 +1,237 @@
+#![cfg(feature = "serde")]
+
+use std::{fmt::Debug, fs, path::PathBuf};
+
+use jito_vault_client::{
+    accounts::{
+        Config, Vault, VaultNcnSlasherOperatorTicket, VaultNcnSlasherTicket, VaultNcnTicket,
+        VaultOperatorDelegation, VaultStakerWithdrawalTicket, VaultUpdateStateTracker,
+        CONFIG_DISCRIMINATOR, VAULT_DISCRIMINATOR, VAULT_NCN_SLASHER_OPERATOR_TICKET_DISCRIMINATOR,
+        VAULT_NCN_SLASHER_TICKET_DISCRIMINATOR, VAULT_NCN_TICKET_DISCRIMINATOR,
+        VAULT_OPERATOR_DELEGATION_DISCRIMINATOR, VAULT_STAKER_WITHDRAWAL_TICKET_DISCRIMINATOR,
+        VAULT_UPDATE_STATE_TRACKER_DISCRIMINATOR,
+    },
+    types::{DelegationState, SlotToggle},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use solana_program::pubkey::Pubkey;
+
+// compares against tests/snapshots/<name>.json, rewrite them with UPDATE_SNAPSHOTS=1
+fn assert_snapshot<T: Serialize + DeserializeOwned + PartialEq + Debug>(name: &str, value: &T) {
+    let full = serde_json::to_value(value).unwrap();
+    let json = serde_json::to_string_pretty(&full).unwrap() + "\n";
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{name}.json"));
+
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, &json).unwrap();
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("missing {}, run with UPDATE_SNAPSHOTS=1", path.display()));
+    assert_eq!(json, expected, "{name} snapshot changed");
+    assert_eq!(&serde_json::from_value::<T>(full).unwrap(), value);
+}
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn slot_toggle() -> SlotToggle {
+    SlotToggle {
+        slot_added: 100,
+        slot_removed: 0,
+        reserved: [0; 32],
+    }
+}
+
+fn delegation_state() -> DelegationState {
+    DelegationState {
+        staked_amount: 1_000_000,
+        enqueued_for_cooldown_amount: 2_000,
+        cooling_down_amount: 3_000,
+        reserved: [0; 256],
+    }
+}
+
+#[test]
+fn slot_toggle_json() {
+    assert_snapshot("slot_toggle", &slot_toggle());
+}
+
+#[test]
+fn delegation_state_json() {
+    assert_snapshot("delegation_state", &delegation_state());
+}
+
+#[test]
+fn config_json() {
+    assert_snapshot(
+        "config",
+        &Config {
+            discriminator: CONFIG_DISCRIMINATOR,
+            admin: key(1),
+            restaking_program: key(2),
+            epoch_length: 432_000,
+            num_vaults: 3,
+            deposit_withdrawal_fee_cap_bps: 2_000,
+            fee_rate_of_change_bps: 2_500,
+            fee_bump_bps: 10,
+            program_fee_bps: 10,
+            program_fee_wallet: key(3),
+            fee_admin: key(4),
+            bump: 255,
+            reserved: [0; 229],
+        },
+    );
+}
+
+#[test]
+fn vault_json() {
+    assert_snapshot(
+        "vault",
+        &Vault {
+            discriminator: VAULT_DISCRIMINATOR,
+            base: key(1),
+            vrt_mint: key(2),
+            supported_mint: key(3),
+            vrt_supply: 1_000_000,
+            tokens_deposited: 1_005_000,
+            deposit_capacity: u64::MAX,
+            delegation_state: delegation_state(),
+            additional_assets_need_unstaking: 0,
+            vrt_enqueued_for_cooldown_amount: 0,
+            vrt_cooling_down_amount: 0,
+            vrt_ready_to_claim_amount: 0,
+            admin: key(4),
+            delegation_admin: key(4),
+            operator_admin: key(4),
+            ncn_admin: key(4),
+            slasher_admin: key(4),
+            capacity_admin: key(4),
+            fee_admin: key(4),
+            delegate_asset_admin: key(4),
+            fee_wallet: key(5),
+            mint_burn_admin: Pubkey::default(),
+            metadata_admin: key(4),
+            vault_index: 7,
+            ncn_count: 1,
+            operator_count: 2,
+            slasher_count: 0,
+            last_fee_change_slot: 0,
+            last_full_state_update_slot: 200,
+            deposit_fee_bps: 0,
+            withdrawal_fee_bps: 0,
+            next_withdrawal_fee_bps: 0,
+            reward_fee_bps: 500,
+            program_fee_bps: 10,
+            bump: 254,
+            is_paused: false,
+            last_start_state_update_slot: 199,
+            reserved: [0; 251],
+        },
+    );
+}
+
+#[test]
+fn vault_ncn_ticket_json() {
+    assert_snapshot(
+        "vault_ncn_ticket",
+        &VaultNcnTicket {
+            discriminator: VAULT_NCN_TICKET_DISCRIMINATOR,
+            vault: key(1),
+            ncn: key(2),
+            index: 0,
+            state: slot_toggle(),
+            bump: 253,
+            reserved: [0; 263],
+        },
+    );
+}
+
+#[test]
+fn vault_operator_delegation_json() {
+    assert_snapshot(
+        "vault_operator_delegation",
+        &VaultOperatorDelegation {
+            discriminator: VAULT_OPERATOR_DELEGATION_DISCRIMINATOR,
+            vault: key(1),
+            operator: key(2),
+            delegation_state: delegation_state(),
+            last_update_slot: 200,
+            index: 1,
+            bump: 252,
+            reserved: [0; 263],
+        },
+    );
+}
+
+#[test]
+fn vault_ncn_slasher_ticket_json() {
+    assert_snapshot(
+        "vault_ncn_slasher_ticket",
+        &VaultNcnSlasherTicket {
+            discriminator: VAULT_NCN_SLASHER_TICKET_DISCRIMINATOR,
+            vault: key(1),
+            ncn: key(2),
+            slasher: key(3),
+            max_slashable_per_epoch: 10_000,
+            index: 0,
+            state: slot_toggle(),
+            bump: 251,
+            reserved: [0; 263],
+        },
+    );
+}
+
+#[test]
+fn vault_ncn_slasher_operator_ticket_json() {
+    assert_snapshot(
+        "vault_ncn_slasher_operator_ticket",
+        &VaultNcnSlasherOperatorTicket {
+            discriminator: VAULT_NCN_SLASHER_OPERATOR_TICKET_DISCRIMINATOR,
+            vault: key(1),
+            ncn: key(2),
+            slasher: key(3),
+            operator: key(4),
+            epoch: 5,
+            slashed: 500,
+            bump: 250,
+            reserved: [0; 263],
+        },
+    );
+}
+
+#[test]
+fn vault_staker_withdrawal_ticket_json() {
+    assert_snapshot(
+        "vault_staker_withdrawal_ticket",
+        &VaultStakerWithdrawalTicket {
+            discriminator: VAULT_STAKER_WITHDRAWAL_TICKET_DISCRIMINATOR,
+            vault: key(1),
+            staker: key(2),
+            base: key(3),
+            vrt_amount: 1_000,
+            slot_unstaked: 300,
+            bump: 249,
+            reserved: [0; 263],
+        },
+    );
+}
+
+#[test]
+fn vault_update_state_tracker_json() {
+    assert_snapshot(
+        "vault_update_state_tracker",
+        &VaultUpdateStateTracker {
+            discriminator: VAULT_UPDATE_STATE_TRACKER_DISCRIMINATOR,
+            vault: key(1),
+            ncn_epoch: 5,
+            last_updated_index: 1,
+            delegation_state: delegation_state(),
+            withdrawal_allocation_method: 0,
+            reserved: [0; 263],
+        },
+    );
+}
@@ -0,0 +1,189 @@
+use std::{io::ErrorKind, mem::size_of};
+
+use borsh::BorshSerialize;
+use jito_vault_client::{
+    accounts::{
+        Vault, VaultNcnSlasherTicket, VaultOperatorDelegation, VaultUpdateStateTracker,
+        VAULT_DISCRIMINATOR, VAULT_NCN_SLASHER_TICKET_DISCRIMINATOR,
+        VAULT_OPERATOR_DELEGATION_DISCRIMINATOR, VAULT_UPDATE_STATE_TRACKER_DISCRIMINATOR,
+    },
+    types::{DelegationState, SlotToggle},
+    zero_copy::{
+        AccountRef, VaultNcnSlasherTicketRef, VaultOperatorDelegationRef, VaultRef,
+        VaultUpdateStateTrackerRef,
+    },
+};
+use solana_program::pubkey::Pubkey;
+
+fn key(n: u8) -> Pubkey {
+    Pubkey::new_from_array([n; 32])
+}
+
+fn delegation_state() -> DelegationState {
+    DelegationState {
+        staked_amount: 1_000_000,
+        enqueued_for_cooldown_amount: 2_000,
+        cooling_down_amount: 3_000,
+        reserved: [0; 256],
+    }
+}
+
+// borsh layout, offset by one byte to show views need no alignment
+fn account_data(account: &impl BorshSerialize) -> Vec<u8> {
+    let mut data = vec![0xff];
+    account.serialize(&mut data).unwrap();
+    data
+}
+
+#[test]
+fn vault_view_matches_borsh_layout() {
+    let vault = Vault {
+        discriminator: VAULT_DISCRIMINATOR,
+        base: key(1),
+        vrt_mint: key(2),
+        supported_mint: key(3),
+        vrt_supply: 4,
+        tokens_deposited: 5,
+        deposit_capacity: 6,
+        delegation_state: delegation_state(),
+        additional_assets_need_unstaking: 7,
+        vrt_enqueued_for_cooldown_amount: 8,
+        vrt_cooling_down_amount: 9,
+        vrt_ready_to_claim_amount: 10,
+        admin: key(11),
+        delegation_admin: key(12),
+        operator_admin: key(13),
+        ncn_admin: key(14),
+        slasher_admin: key(15),
+        capacity_admin: key(16),
+        fee_admin: key(17),
+        delegate_asset_admin: key(18),
+        fee_wallet: key(19),
+        mint_burn_admin: key(20),
+        metadata_admin: key(21),
+        vault_index: 22,
+        ncn_count: 23,
+        operator_count: 24,
+        slasher_count: 25,
+        last_fee_change_slot: 26,
+        last_full_state_update_slot: 27,
+        deposit_fee_bps: 28,
+        withdrawal_fee_bps: 29,
+        next_withdrawal_fee_bps: 30,
+        reward_fee_bps: 31,
+        program_fee_bps: 32,
+        bump: 33,
+        is_paused: true,
+        last_start_state_update_slot: 34,
+        reserved: [0; 251],
+    };
+    let data = account_data(&vault);
+    assert_eq!(data.len() - 1, Vault::LEN);
+    assert_eq!(size_of::<VaultRef>() + vault.reserved.len(), Vault::LEN);
+
+    let view = VaultRef::try_from_bytes(&data[1..]).unwrap();
+    assert_eq!(view.supported_mint, vault.supported_mint);
+    assert_eq!(
+        u64::from(view.delegation_state.cooling_down_amount),
+        vault.delegation_state.cooling_down_amount
+    );
+    assert_eq!(view.metadata_admin, vault.metadata_admin);
+    assert_eq!(u16::from(view.reward_fee_bps), vault.reward_fee_bps);
+    assert_eq!(u16::from(view.program_fee_bps), vault.program_fee_bps);
+    assert_eq!(view.bump, vault.bump);
+    assert!(bool::from(view.is_paused));
+    assert_eq!(
+        u64::from(view.last_start_state_update_slot),
+        vault.last_start_state_update_slot
+    );
+}
+
+#[test]
+fn delegation_views_match_borsh_layout() {
+    let delegation = VaultOperatorDelegation {
+        discriminator: VAULT_OPERATOR_DELEGATION_DISCRIMINATOR,
+        vault: key(1),
+        operator: key(2),
+        delegation_state: delegation_state(),
+        last_update_slot: 3,
+        index: 4,
+        bump: 5,
+        reserved: [0; 263],
+    };
+    let data = account_data(&delegation);
+    let view = VaultOperatorDelegationRef::try_from_bytes(&data[1..]).unwrap();
+    assert_eq!(view.vault, delegation.vault);
+    assert_eq!(view.operator, delegation.operator);
+    assert_eq!(u64::from(view.delegation_state.staked_amount), 1_000_000);
+    assert_eq!(u64::from(view.index), delegation.index);
+    assert_eq!(view.bump, delegation.bump);
+
+    let tracker = VaultUpdateStateTracker {
+        discriminator: VAULT_UPDATE_STATE_TRACKER_DISCRIMINATOR,
+        vault: key(1),
+        ncn_epoch: 2,
+        last_updated_index: 3,
+        delegation_state: delegation_state(),
+        withdrawal_allocation_method: 4,
+        reserved: [0; 263],
+    };
+    let data = account_data(&tracker);
+    let view = VaultUpdateStateTrackerRef::try_from_bytes(&data[1..]).unwrap();
+    assert_eq!(
+        u64::from(view.delegation_state.enqueued_for_cooldown_amount),
+        2_000
+    );
+    assert_eq!(view.withdrawal_allocation_method, 4);
+}
+
+#[test]
+fn slasher_ticket_view_matches_borsh_layout() {
+    let ticket = VaultNcnSlasherTicket {
+        discriminator: VAULT_NCN_SLASHER_TICKET_DISCRIMINATOR,
+        vault: key(1),
+        ncn: key(2),
+        slasher: key(3),
+        max_slashable_per_epoch: 4,
+        index: 5,
+        state: SlotToggle {
+            slot_added: 6,
+            slot_removed: 7,
+            reserved: [0; 32],
+        },
+        bump: 8,
+        reserved: [0; 263],
+    };
+    let data = account_data(&ticket);
+    let view = VaultNcnSlasherTicketRef::try_from_bytes(&data[1..]).unwrap();
+    assert_eq!(u64::from(view.max_slashable_per_epoch), 4);
+    assert_eq!(u64::from(view.state.slot_removed), 7);
+    assert_eq!(view.bump, ticket.bump);
+}
+
+#[test]
+fn rejects_other_account_types() {
+    let delegation = VaultOperatorDelegation {
+        discriminator: VAULT_OPERATOR_DELEGATION_DISCRIMINATOR,
+        vault: key(1),
+        operator: key(2),
+        delegation_state: delegation_state(),
+        last_update_slot: 3,
+        index: 4,
+        bump: 5,
+        reserved: [0; 263],
+    };
+    let mut data = account_data(&delegation);
+    data.resize(Vault::LEN + 1, 0);
+
+    // a VaultOperatorDelegation padded to the size of a Vault
+    assert_eq!(
+        VaultRef::try_from_bytes(&data[1..]).unwrap_err().kind(),
+        ErrorKind::InvalidData
+    );
+    assert_eq!(
+        VaultOperatorDelegationRef::try_from_bytes(&data[1..100])
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InvalidData
+    );
+}
@@ -2,11 +2,34 @@
 
 use anchor_lang::{prelude::*, solana_program};
 use anchor_spl::token_interface::TokenInterface;
-use jito_restaking_client::programs::JITO_RESTAKING_ID;
-use jito_vault_client::programs::JITO_VAULT_ID;
+use jito_restaking_client::{
+    pda as restaking_pda, programs::JITO_RESTAKING_ID, zero_copy::SlotToggleRef,
+};
+use jito_vault_client::{pda as vault_pda, programs::JITO_VAULT_ID};
+use std::cell::Ref;
+
+pub mod pda;
 
 declare_id!("FMtP7JSgYneYu36nisXubFWTWw6LGC9EFJ6YhjAq6CQr");
 
+pub const MAX_BPS: u16 = 10_000;
+// a bit more than 2/3 of the stake
+pub const DEFAULT_CONSENSUS_THRESHOLD_BPS: u16 = 6_667;
+// a weight of WEIGHT_PRECISION counts 1 token as 1 unit of voting power
+pub const WEIGHT_PRECISION: u64 = 1_000_000_000;
+// keeps stake weights of any realistic delegation within u64
+pub const MAX_WEIGHT: u64 = 1_000 * WEIGHT_PRECISION;
+// snapshots track the vaults of an operator in a u64 bitmap
+pub const MAX_VAULTS: u64 = 64;
+// distinct values a ballot box can tally
+pub const MAX_BALLOTS: usize = 16;
+// longest voting window a proposal can have
+pub const MAX_VOTING_EPOCHS: u64 = 32;
+// a claim bitmap stays within the size an account can be created with
+pub const MAX_CLAIM_BITMAP_LEAVES: u32 = 80_000;
+// leaves paying lamports commit to this in place of a mint
+pub const NATIVE_SOL_MINT: Pubkey = solana_program::system_program::ID;
+
 // from spl-merkle-tree-reference
 type Node = [u8; 32];
 
@@ -23,10 +46,67 @@ pub fn recompute(mut leaf: Node, proof: &[Node], index: u32) -> Node {
     leaf
 }
 
+pub const LEAF_PREFIX: &[u8] = &[0x00];
+pub const NODE_PREFIX: &[u8] = &[0x01];
+
+// how leaves and nodes of a rewards tree are hashed
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq, Debug)]
+pub enum HashVersion {
+    // keccak(owner || total_rewards_le) and keccak(left || right)
+    V0,
+    // keccak(0x00 || owner || total_rewards_le) and keccak(0x01 || left || right)
+    V1,
+    // keccak(0x00 || owner || mint || total_rewards_le) and keccak(0x01 || left || right)
+    V2,
+}
+
+impl HashVersion {
+    pub const CURRENT: HashVersion = HashVersion::V2;
+
+    // `mint` is only committed to from V2 on
+    pub fn hash_leaf(self, owner: &Pubkey, mint: &Pubkey, total_rewards: u64) -> Node {
+        let amount = total_rewards.to_le_bytes();
+        match self {
+            HashVersion::V0 => solana_program::keccak::hashv(&[owner.as_ref(), &amount]),
+            HashVersion::V1 => {
+                solana_program::keccak::hashv(&[LEAF_PREFIX, owner.as_ref(), &amount])
+            }
+            HashVersion::V2 => solana_program::keccak::hashv(&[
+                LEAF_PREFIX,
+                owner.as_ref(),
+                mint.as_ref(),
+                &amount,
+            ]),
+        }
+        .to_bytes()
+    }
+
+    pub fn recompute(self, mut leaf: Node, proof: &[Node], index: u32) -> Node {
+        match self {
+            HashVersion::V0 => recompute(leaf, proof, index),
+            HashVersion::V1 | HashVersion::V2 => {
+                for (i, s) in proof.iter().enumerate() {
+                    let (left, right) = if index >> i & 1 == 0 {
+                        (&leaf, s)
+                    } else {
+                        (s, &leaf)
+                    };
+                    leaf = solana_program::keccak::hashv(&[NODE_PREFIX, left, right]).to_bytes();
+                }
+                leaf
+            }
+        }
+    }
+}
+
 #[program]
 pub mod mini_ncn {
-    use jito_restaking_client::accounts::{Operator, OperatorVaultTicket};
-    use jito_vault_client::accounts::{Vault, VaultOperatorDelegation};
+    use jito_restaking_client::zero_copy::{
+        ConfigRef, NcnOperatorStateRef, OperatorRef, OperatorVaultTicketRef,
+    };
+    use jito_vault_client::zero_copy::{
+        VaultNcnSlasherTicketRef, VaultOperatorDelegationRef, VaultRef,
+    };
 
     use super::*;
 
@@ -55,7 +135,7 @@ pub mod mini_ncn {
             },
         )
         .invoke_signed(&[&[
-            b"ncn_admin",
+            pda::NCN_ADMIN_SEED,
             ctx.accounts.ncn.key().as_ref(),
             &[ctx.bumps.ncn_admin],
         ]])?;
@@ -63,15 +143,97 @@ pub mod mini_ncn {
         let config = &mut ctx.accounts.config;
         config.ncn = ctx.accounts.ncn.key();
         config.authority = ctx.accounts.authority.key();
+        config.consensus_threshold_bps = DEFAULT_CONSENSUS_THRESHOLD_BPS;
+        config.treasury = ctx.accounts.authority.key();
+        config.pending_authority = None;
+        config.proposer = None;
+        config.rewards_admin = None;
+        config.pauser = None;
+        config.paused = false;
 
         Ok(())
     }
 
-    pub fn initialize_ballot_box(ctx: Context<InitializeBallotBox>) -> Result<()> {
-        let config = &ctx.accounts.config;
+    // the new authority takes over once it signs accept_authority, so control
+    // can't be handed to a key nobody holds
+    // None cancels a pending handover
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.pending_authority = new_authority;
+
+        emit!(AuthorityProposed {
+            config: config.key(),
+            authority: config.authority,
+            pending_authority: new_authority,
+        });
 
-        let ballot_box = &mut ctx.accounts.ballot_box;
-        ballot_box.config = config.key();
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let previous_authority = config.authority;
+        config.authority = ctx.accounts.pending_authority.key();
+        config.pending_authority = None;
+
+        emit!(AuthorityAccepted {
+            config: config.key(),
+            previous_authority,
+            authority: config.authority,
+        });
+
+        Ok(())
+    }
+
+    // an unset role falls back to the authority
+    pub fn set_role(ctx: Context<SetRole>, role: Role, holder: Option<Pubkey>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let slot = match role {
+            Role::Proposer => &mut config.proposer,
+            Role::RewardsAdmin => &mut config.rewards_admin,
+            Role::Pauser => &mut config.pauser,
+        };
+        let previous_holder = std::mem::replace(slot, holder);
+
+        emit!(RoleChanged {
+            config: config.key(),
+            role,
+            previous_holder,
+            holder,
+        });
+
+        Ok(())
+    }
+
+    // stops proposals, votes, claims and rewards payouts
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.paused = paused;
+
+        emit!(PausedChanged {
+            config: config.key(),
+            paused,
+        });
+
+        Ok(())
+    }
+
+    pub fn set_consensus_threshold(
+        ctx: Context<SetConsensusThreshold>,
+        consensus_threshold_bps: u16,
+    ) -> Result<()> {
+        check_consensus_threshold(consensus_threshold_bps)?;
+
+        ctx.accounts.config.consensus_threshold_bps = consensus_threshold_bps;
+
+        Ok(())
+    }
+
+    pub fn set_treasury(ctx: Context<SetTreasury>, treasury: Pubkey) -> Result<()> {
+        ctx.accounts.config.treasury = treasury;
 
         Ok(())
     }
@@ -121,17 +283,17 @@ pub mod mini_ncn {
         )
         .invoke_signed(&[
             &[
-                b"mini_ncn",
+                pda::CONFIG_SEED,
                 ctx.accounts.config.ncn.as_ref(),
                 &[ctx.bumps.config],
             ],
             &[
-                b"vault_admin",
+                pda::VAULT_ADMIN_SEED,
                 ctx.accounts.vault.key().as_ref(),
                 &[ctx.bumps.vault_admin],
             ],
             &[
-                b"vrt_mint",
+                pda::VRT_MINT_SEED,
                 ctx.accounts.st_mint.key().as_ref(),
                 &[ctx.bumps.vrt_mint],
             ],
@@ -140,7 +302,129 @@ pub mod mini_ncn {
         Ok(())
     }
 
+    pub fn set_weight(ctx: Context<SetWeight>, weight: u64) -> Result<()> {
+        require!(weight <= MAX_WEIGHT, MiniNcnError::InvalidWeight);
+
+        let weight_table = &mut ctx.accounts.weight_table;
+        weight_table.config = ctx.accounts.config.key();
+        weight_table.st_mint = ctx.accounts.st_mint.key();
+        weight_table.weight = weight;
+
+        Ok(())
+    }
+
+    pub fn register_vault(ctx: Context<RegisterVault>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        require!(config.vault_count < MAX_VAULTS, MiniNcnError::TooManyVaults);
+
+        // copied out, the vault is passed to the CPIs below
+        let supported_mint = {
+            let vault: Ref<VaultRef> = vault_account_ref(&ctx.accounts.vault)?;
+            vault.supported_mint
+        };
+        require_keys_eq!(
+            supported_mint, ctx.accounts.weight_table.st_mint,
+            MiniNcnError::InvalidWeightTable
+        );
+
+        jito_restaking_client::instructions::InitializeNcnVaultTicketCpi::new(
+            &ctx.accounts.jito_restaking_program,
+            jito_restaking_client::instructions::InitializeNcnVaultTicketCpiAccounts {
+                config: &ctx.accounts.jito_restaking_config.to_account_info(),
+                ncn: &ctx.accounts.ncn.to_account_info(),
+                vault: &ctx.accounts.vault.to_account_info(),
+                ncn_vault_ticket: &ctx.accounts.ncn_vault_ticket.to_account_info(),
+                admin: &ctx.accounts.ncn_admin.to_account_info(),
+                payer: &ctx.accounts.payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+            },
+        )
+        .invoke_signed(&[&[
+            pda::NCN_ADMIN_SEED,
+            ctx.accounts.ncn.key().as_ref(),
+            &[ctx.bumps.ncn_admin],
+        ]])?;
+
+        // the vault side is signed by the vault ncn_admin, which is our vault_admin for our own vault
+        let (_, vault_admin_bump) = pda::find_vault_admin_address(&ctx.accounts.vault.key());
+        jito_vault_client::instructions::InitializeVaultNcnTicketCpi::new(
+            &ctx.accounts.jito_vault_program,
+            jito_vault_client::instructions::InitializeVaultNcnTicketCpiAccounts {
+                config: &ctx.accounts.jito_vault_config.to_account_info(),
+                vault: &ctx.accounts.vault.to_account_info(),
+                ncn: &ctx.accounts.ncn.to_account_info(),
+                ncn_vault_ticket: &ctx.accounts.ncn_vault_ticket.to_account_info(),
+                vault_ncn_ticket: &ctx.accounts.vault_ncn_ticket.to_account_info(),
+                admin: &ctx.accounts.vault_ncn_admin.to_account_info(),
+                payer: &ctx.accounts.payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+            },
+        )
+        .invoke_signed(&[&[
+            pda::VAULT_ADMIN_SEED,
+            ctx.accounts.vault.key().as_ref(),
+            &[vault_admin_bump],
+        ]])?;
+
+        let vault_entry = &mut ctx.accounts.vault_entry;
+        vault_entry.config = config.key();
+        vault_entry.vault = ctx.accounts.vault.key();
+        vault_entry.st_mint = supported_mint;
+        vault_entry.index = config.vault_count;
+
+        config.vault_count += 1;
+
+        Ok(())
+    }
+
+    // tickets can only be warmed up in the epoch after they are initialized
+    pub fn warmup_vault(ctx: Context<WarmupVault>) -> Result<()> {
+        jito_restaking_client::instructions::WarmupNcnVaultTicketCpi::new(
+            &ctx.accounts.jito_restaking_program,
+            jito_restaking_client::instructions::WarmupNcnVaultTicketCpiAccounts {
+                config: &ctx.accounts.jito_restaking_config.to_account_info(),
+                ncn: &ctx.accounts.ncn.to_account_info(),
+                vault: &ctx.accounts.vault.to_account_info(),
+                ncn_vault_ticket: &ctx.accounts.ncn_vault_ticket.to_account_info(),
+                admin: &ctx.accounts.ncn_admin.to_account_info(),
+            },
+        )
+        .invoke_signed(&[&[
+            pda::NCN_ADMIN_SEED,
+            ctx.accounts.ncn.key().as_ref(),
+            &[ctx.bumps.ncn_admin],
+        ]])?;
+
+        let (_, vault_admin_bump) = pda::find_vault_admin_address(&ctx.accounts.vault.key());
+        jito_vault_client::instructions::WarmupVaultNcnTicketCpi::new(
+            &ctx.accounts.jito_vault_program,
+            jito_vault_client::instructions::WarmupVaultNcnTicketCpiAccounts {
+                config: &ctx.accounts.jito_vault_config.to_account_info(),
+                vault: &ctx.accounts.vault.to_account_info(),
+                ncn: &ctx.accounts.ncn.to_account_info(),
+                vault_ncn_ticket: &ctx.accounts.vault_ncn_ticket.to_account_info(),
+                admin: &ctx.accounts.vault_ncn_admin.to_account_info(),
+            },
+        )
+        .invoke_signed(&[&[
+            pda::VAULT_ADMIN_SEED,
+            ctx.accounts.vault.key().as_ref(),
+            &[vault_admin_bump],
+        ]])?;
+
+        Ok(())
+    }
+
     pub fn initialize_operator(ctx: Context<InitializeOperator>) -> Result<()> {
+        // fail early on anything but an operator of the signing admin
+        {
+            let operator: Ref<OperatorRef> = restaking_account_ref(&ctx.accounts.operator)?;
+            require_eq!(
+                operator.admin, ctx.accounts.operator_admin.key(),
+                MiniNcnError::InvalidOperator
+            );
+        }
+
         jito_restaking_client::instructions::InitializeOperatorVaultTicketCpi::new(
             &ctx.accounts.jito_restaking_program,
             jito_restaking_client::instructions::InitializeOperatorVaultTicketCpiAccounts {
@@ -172,18 +456,70 @@ pub mod mini_ncn {
             },
         )
         .invoke_signed(&[&[
-            b"vault_admin",
+            pda::VAULT_ADMIN_SEED,
             ctx.accounts.vault.key().as_ref(),
             &[ctx.bumps.vault_admin],
         ]])?;
 
+        jito_restaking_client::instructions::InitializeNcnOperatorStateCpi::new(
+            &ctx.accounts.jito_restaking_program,
+            jito_restaking_client::instructions::InitializeNcnOperatorStateCpiAccounts {
+                config: &ctx.accounts.jito_restaking_config.to_account_info(),
+                ncn: &ctx.accounts.ncn.to_account_info(),
+                operator: &ctx.accounts.operator.to_account_info(),
+                ncn_operator_state: &ctx.accounts.ncn_operator_state.to_account_info(),
+                admin: &ctx.accounts.ncn_admin.to_account_info(),
+                payer: &ctx.accounts.payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+            },
+        )
+        .invoke_signed(&[&[
+            pda::NCN_ADMIN_SEED,
+            ctx.accounts.ncn.key().as_ref(),
+            &[ctx.bumps.ncn_admin],
+        ]])?;
+
+        let config = &mut ctx.accounts.config;
         let voter_state = &mut ctx.accounts.voter_state;
-        voter_state.config = ctx.accounts.config.key();
+        voter_state.config = config.key();
         voter_state.operator = ctx.accounts.operator.key();
+        voter_state.index = config.operator_count;
         voter_state.last_voted_epoch = 0;
+        // earlier proposals were decided without this operator
+        voter_state.recorded_proposals = config.proposal_count;
+        voter_state.epochs_participated = 0;
+        voter_state.consecutive_missed_epochs = 0;
+        voter_state.last_missed_epoch = 0;
+        voter_state.missed_votes = 0;
+        voter_state.votes_agreeing = 0;
+        voter_state.participated_stake_weight = 0;
+        voter_state.eligible_stake_weight = 0;
         voter_state.operator_vault_ticket = ctx.accounts.operator_vault_ticket.key();
         voter_state.vault_operator_delegation = ctx.accounts.vault_operator_delegation.key();
 
+        config.operator_count += 1;
+
+        Ok(())
+    }
+
+    // the NCN side of the opt-in, the operator side is done by the operator admin
+    pub fn warmup_operator(ctx: Context<WarmupOperator>) -> Result<()> {
+        jito_restaking_client::instructions::NcnWarmupOperatorCpi::new(
+            &ctx.accounts.jito_restaking_program,
+            jito_restaking_client::instructions::NcnWarmupOperatorCpiAccounts {
+                config: &ctx.accounts.jito_restaking_config.to_account_info(),
+                ncn: &ctx.accounts.ncn.to_account_info(),
+                operator: &ctx.accounts.operator.to_account_info(),
+                ncn_operator_state: &ctx.accounts.ncn_operator_state.to_account_info(),
+                admin: &ctx.accounts.ncn_admin.to_account_info(),
+            },
+        )
+        .invoke_signed(&[&[
+            pda::NCN_ADMIN_SEED,
+            ctx.accounts.ncn.key().as_ref(),
+            &[ctx.bumps.ncn_admin],
+        ]])?;
+
         Ok(())
     }
 
@@ -200,7 +536,7 @@ pub mod mini_ncn {
             jito_vault_client::instructions::AddDelegationInstructionArgs { amount },
         )
         .invoke_signed(&[&[
-            b"vault_admin",
+            pda::VAULT_ADMIN_SEED,
             ctx.accounts.vault.key().as_ref(),
             &[ctx.bumps.vault_admin],
         ]])?;
@@ -208,413 +544,2286 @@ pub mod mini_ncn {
         Ok(())
     }
 
-    pub fn propose(ctx: Context<Propose>, new_root: [u8; 32]) -> Result<()> {
-        let ballot_box = &mut ctx.accounts.ballot_box;
+    pub fn initialize_epoch_snapshot(
+        ctx: Context<InitializeEpochSnapshot>,
+        epoch: u64,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        require_eq!(epoch, clock.epoch, MiniNcnError::InvalidEpoch);
+
+        let epoch_snapshot = &mut ctx.accounts.epoch_snapshot;
+        epoch_snapshot.config = ctx.accounts.config.key();
+        epoch_snapshot.epoch = epoch;
+        // operators registered after this point are not part of this epoch
+        epoch_snapshot.operator_count = ctx.accounts.config.operator_count;
+        epoch_snapshot.vault_count = ctx.accounts.config.vault_count;
+        epoch_snapshot.operators_snapshotted = 0;
+        epoch_snapshot.total_stake_weight = 0;
+
+        Ok(())
+    }
+
+    pub fn initialize_operator_snapshot(ctx: Context<InitializeOperatorSnapshot>) -> Result<()> {
+        let epoch_snapshot = &mut ctx.accounts.epoch_snapshot;
+
+        let clock = Clock::get()?;
+        require_eq!(clock.epoch, epoch_snapshot.epoch, MiniNcnError::InvalidEpoch);
+        require!(
+            ctx.accounts.voter_state.index < epoch_snapshot.operator_count,
+            MiniNcnError::OperatorNotInSnapshot
+        );
+
+        let operator_snapshot = &mut ctx.accounts.operator_snapshot;
+        operator_snapshot.epoch_snapshot = epoch_snapshot.key();
+        operator_snapshot.operator = ctx.accounts.operator.key();
+        operator_snapshot.epoch = epoch_snapshot.epoch;
+        operator_snapshot.vault_bitmap = 0;
+        operator_snapshot.stake_weight = 0;
+
+        // nothing to crank if the NCN has no vaults yet
+        if operator_snapshot.is_finalized(epoch_snapshot.vault_count) {
+            epoch_snapshot.operators_snapshotted += 1;
+        }
+
+        Ok(())
+    }
+
+    pub fn snapshot_vault_operator_delegation(
+        ctx: Context<SnapshotVaultOperatorDelegation>,
+    ) -> Result<()> {
+        let epoch_snapshot = &mut ctx.accounts.epoch_snapshot;
+        let operator_snapshot = &mut ctx.accounts.operator_snapshot;
+        let vault_entry = &ctx.accounts.vault_entry;
+
+        let clock = Clock::get()?;
+        require_eq!(clock.epoch, epoch_snapshot.epoch, MiniNcnError::InvalidEpoch);
+        require!(
+            vault_entry.index < epoch_snapshot.vault_count,
+            MiniNcnError::VaultNotInSnapshot
+        );
+
+        let vault_bit = 1u64 << vault_entry.index;
+        require!(
+            operator_snapshot.vault_bitmap & vault_bit == 0,
+            MiniNcnError::VaultAlreadySnapshotted
+        );
+
+        // stake counts only while the operator is active in the vault, as vote
+        // requires of the config's own vault
+        let ticket_active = !ctx.accounts.operator_vault_ticket.data_is_empty() && {
+            let jito_restaking_config: Ref<ConfigRef> =
+                restaking_account_ref(&ctx.accounts.jito_restaking_config)?;
+            let operator_vault_ticket: Ref<OperatorVaultTicketRef> =
+                restaking_account_ref(&ctx.accounts.operator_vault_ticket)?;
+            is_active(
+                &operator_vault_ticket.state,
+                clock.slot,
+                jito_restaking_config.epoch_length.into(),
+            )?
+        };
+
+        // an operator without delegation from this vault has no stake in it
+        let staked_amount = if !ticket_active
+            || ctx.accounts.vault_operator_delegation.data_is_empty()
+        {
+            0
+        } else {
+            let vault_operator_delegation: Ref<VaultOperatorDelegationRef> =
+                vault_account_ref(&ctx.accounts.vault_operator_delegation)?;
+            require_eq!(
+                vault_operator_delegation.operator, ctx.accounts.operator.key(),
+                MiniNcnError::InvalidOperator
+            );
+            require_eq!(
+                vault_operator_delegation.vault, ctx.accounts.vault.key(),
+                MiniNcnError::InvalidVault
+            );
+
+            vault_operator_delegation.delegation_state.staked_amount.into()
+        };
+
+        let stake_weight = ctx.accounts.weight_table.stake_weight(staked_amount)?;
+
+        operator_snapshot.vault_bitmap |= vault_bit;
+        operator_snapshot.vault_stake_weights[vault_entry.index as usize] = stake_weight;
+        operator_snapshot.stake_weight = operator_snapshot
+            .stake_weight
+            .checked_add(stake_weight)
+            .ok_or(MiniNcnError::StakeWeightOverflow)?;
+        epoch_snapshot.total_stake_weight = epoch_snapshot
+            .total_stake_weight
+            .checked_add(stake_weight)
+            .ok_or(MiniNcnError::StakeWeightOverflow)?;
+
+        if operator_snapshot.is_finalized(epoch_snapshot.vault_count) {
+            epoch_snapshot.operators_snapshotted += 1;
+        }
+
+        Ok(())
+    }
+
+    pub fn propose(ctx: Context<Propose>, args: ProposeArgs) -> Result<()> {
+        let config = &mut ctx.accounts.config;
         let clock = Clock::get()?;
 
-        require!(ballot_box.epoch < clock.epoch, MiniNcnError::InvalidEpoch);
         require!(
-            ballot_box.proposed_rewards_root.is_none(),
-            MiniNcnError::NonEmptyProposedRoot
+            args.voting_epochs > 0 && args.voting_epochs <= MAX_VOTING_EPOCHS,
+            MiniNcnError::InvalidVotingWindow
         );
+        args.kind.validate()?;
+
+        // voting weights are frozen once the proposal is made
+        let epoch_snapshot = &ctx.accounts.epoch_snapshot;
+        require_eq!(epoch_snapshot.epoch, clock.epoch, MiniNcnError::InvalidEpoch);
+        require!(
+            epoch_snapshot.is_finalized(),
+            MiniNcnError::EpochSnapshotNotFinalized
+        );
+
+        // a proposal may require a different threshold than the NCN default
+        let consensus_threshold_bps = args
+            .consensus_threshold_bps
+            .unwrap_or(config.consensus_threshold_bps);
+        check_consensus_threshold(consensus_threshold_bps)?;
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.config = config.key();
+        proposal.id = config.proposal_count;
+        proposal.kind = args.kind;
+        proposal.status = ProposalStatus::Voting;
+        proposal.snapshot_epoch = clock.epoch;
+        proposal.end_epoch = clock
+            .epoch
+            .checked_add(args.voting_epochs - 1)
+            .ok_or(MiniNcnError::InvalidVotingWindow)?;
 
-        // TODO: maybe set consensus threshold here
-        ballot_box.propose(clock.epoch, new_root);
+        let ballot_box = &mut ctx.accounts.ballot_box;
+        ballot_box.proposal = proposal.key();
+        ballot_box.operators_voted = 0;
+        ballot_box.approve_stake = 0;
+        ballot_box.reject_stake = 0;
+        ballot_box.abstain_stake = 0;
+        ballot_box.ballot_tallies = vec![];
+        ballot_box.winning_ballot = None;
+        ballot_box.consensus_threshold_bps = consensus_threshold_bps;
+        ballot_box.hash_version = HashVersion::CURRENT;
+
+        config.proposal_count += 1;
 
         Ok(())
     }
 
 
     pub fn vote(ctx: Context<Vote>, args: VoteArgs) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
         let ballot_box = &mut ctx.accounts.ballot_box;
         let voter_state = &mut ctx.accounts.voter_state;
 
         let clock = Clock::get()?;
-        require_eq!(clock.epoch, ballot_box.epoch, MiniNcnError::InvalidEpoch);
         require!(
-            clock.epoch > voter_state.last_voted_epoch,
-            MiniNcnError::InvalidEpoch
+            proposal.status == ProposalStatus::Voting,
+            MiniNcnError::ProposalNotVoting
+        );
+        require!(
+            clock.epoch <= proposal.end_epoch,
+            MiniNcnError::VotingWindowClosed
         );
-
-        // Vault-Operator
-        {
-            let _vault = Vault::from_bytes(&ctx.accounts.vault.try_borrow_data()?)?;
-        }
 
         {
-            let operator = Operator::from_bytes(&ctx.accounts.operator.try_borrow_data()?)?;
+            let operator: Ref<OperatorRef> = restaking_account_ref(&ctx.accounts.operator)?;
             require_eq!(
                 operator.admin, ctx.accounts.operator_admin.key(),
                 MiniNcnError::InvalidOperator
             );
         }
 
+        // both the NCN and the operator must have opted in, and the operator must be active in the vault
         {
-            require_eq!(
-                voter_state.operator_vault_ticket, ctx.accounts.operator_vault_ticket.key(),
-                MiniNcnError::InvalidOperatorVaultTicket
+            let jito_restaking_config: Ref<ConfigRef> =
+                restaking_account_ref(&ctx.accounts.jito_restaking_config)?;
+            let epoch_length = jito_restaking_config.epoch_length.into();
+
+            let ncn_operator_state: Ref<NcnOperatorStateRef> =
+                restaking_account_ref(&ctx.accounts.ncn_operator_state)?;
+            require!(
+                is_active(&ncn_operator_state.ncn_opt_in_state, clock.slot, epoch_length)?,
+                MiniNcnError::NcnOptInNotActive
             );
-            let operator_vault_ticket = OperatorVaultTicket::from_bytes(
-                &ctx.accounts.operator_vault_ticket.try_borrow_data()?,
-            )?;
-            require_eq!(
-                operator_vault_ticket.operator, ctx.accounts.operator.key(),
-                MiniNcnError::InvalidOperatorVaultTicket
+            require!(
+                is_active(&ncn_operator_state.operator_opt_in_state, clock.slot, epoch_length)?,
+                MiniNcnError::OperatorOptInNotActive
             );
-            let operator_vault_ticket = OperatorVaultTicket::from_bytes(
-                &ctx.accounts.operator_vault_ticket.try_borrow_data()?,
-            )?;
+
+            let operator_vault_ticket: Ref<OperatorVaultTicketRef> =
+                restaking_account_ref(&ctx.accounts.operator_vault_ticket)?;
             require_eq!(
                 operator_vault_ticket.operator, ctx.accounts.operator.key(),
                 MiniNcnError::InvalidOperatorVaultTicket
             );
-            require_eq!(
-                operator_vault_ticket.vault, ctx.accounts.vault.key(),
-                MiniNcnError::InvalidOperatorVaultTicket
+            require!(
+                is_active(&operator_vault_ticket.state, clock.slot, epoch_length)?,
+                MiniNcnError::OperatorVaultTicketNotActive
             );
         }
 
-        let vault_operator_delegation = VaultOperatorDelegation::from_bytes(
-            &ctx.accounts.vault_operator_delegation.try_borrow_data()?,
-        )?;
-        require_eq!(
-            vault_operator_delegation.operator, ctx.accounts.operator.key(),
-            MiniNcnError::InvalidOperator
-        );
-        require_eq!(
-            vault_operator_delegation.vault, ctx.accounts.vault.key(),
-            MiniNcnError::InvalidVault
+        let operator_snapshot = &ctx.accounts.operator_snapshot;
+        require!(
+            operator_snapshot.is_finalized(ctx.accounts.epoch_snapshot.vault_count),
+            MiniNcnError::OperatorSnapshotNotFinalized
         );
+        let stake_weight = operator_snapshot.stake_weight;
 
-        ballot_box.operators_voted += 1;
-        if args.approved {
-            ballot_box.approved_votes += vault_operator_delegation.delegation_state.staked_amount;
-        } else {
-            ballot_box.total_votes += vault_operator_delegation.delegation_state.staked_amount;
-        }
+        ballot_box.cast(&args.choice, stake_weight)?;
+
+        // vote_record is only created once, so an operator votes once per proposal
+        let vote_record = &mut ctx.accounts.vote_record;
+        vote_record.proposal = proposal.key();
+        vote_record.operator = ctx.accounts.operator.key();
+        vote_record.choice = args.choice;
+        vote_record.stake_weight = stake_weight;
 
+        if voter_state.epochs_participated == 0 || voter_state.last_voted_epoch != clock.epoch {
+            voter_state.epochs_participated += 1;
+        }
         voter_state.last_voted_epoch = clock.epoch;
 
         Ok(())
     }
 
     pub fn check_consensus(ctx: Context<CheckConsensus>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let proposal = &mut ctx.accounts.proposal;
         let ballot_box = &mut ctx.accounts.ballot_box;
 
-        let proposed_rewards_root = ballot_box
-            .proposed_rewards_root
-            .ok_or(MiniNcnError::EmptyProposedRoot)?;
-
-        let vault = Vault::from_bytes(&ctx.accounts.vault.try_borrow_data()?)?;
+        require!(
+            proposal.status == ProposalStatus::Voting,
+            MiniNcnError::ProposalNotVoting
+        );
 
         let clock = Clock::get()?;
-        let consensus_reached = ballot_box.approved_votes > vault.vrt_supply * 2 / 3;
+        let total_stake_weight = ctx.accounts.epoch_snapshot.total_stake_weight;
+        let winning_ballot = ballot_box.winning_ballot(total_stake_weight);
 
-        if consensus_reached {
+        if let Some(winning_ballot) = winning_ballot {
             msg!("Consensus reached");
 
-            ballot_box.rewards_root = proposed_rewards_root;
-        }
-
-        let vote_window_passed = clock.epoch > ballot_box.epoch;
-        if vote_window_passed || consensus_reached {
-            ballot_box.proposed_rewards_root = None;
+            ballot_box.winning_ballot = Some(winning_ballot);
+
+            // operators agreed on something other than what was proposed
+            if proposal
+                .kind
+                .ballot()
+                .is_some_and(|ballot| ballot != winning_ballot)
+            {
+                proposal.status = ProposalStatus::Rejected;
+                return Ok(());
+            }
+
+            proposal.status = ProposalStatus::Approved;
+            match proposal.kind {
+                ProposalKind::ConfigChange {
+                    consensus_threshold_bps,
+                } => {
+                    config.consensus_threshold_bps = consensus_threshold_bps;
+                }
+                // approved rewards roots are published with initialize_distribution,
+                // stake rewards with initialize_stake_distribution,
+                // approved undelegation proposals are executed by force_undelegate
+                ProposalKind::RewardsRoot(_)
+                | ProposalKind::ForceUndelegation { .. }
+                | ProposalKind::Message(_)
+                | ProposalKind::StakeRewards { .. } => {}
+            }
+        } else if clock.epoch > proposal.end_epoch
+            || !ballot_box.approval_possible(total_stake_weight)
+        {
+            proposal.status = ProposalStatus::Rejected;
         }
 
         Ok(())
     }
 
 
-    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
-        anchor_spl::token_interface::transfer_checked(
-            CpiContext::new(
-                ctx.accounts.rewards_token_program.to_account_info(),
-                anchor_spl::token_interface::TransferChecked {
-                    mint: ctx.accounts.rewards_mint.to_account_info(),
-                    from: ctx.accounts.fund_token_account.to_account_info(),
-                    to: ctx.accounts.rewards_token_account.to_account_info(),
-                    authority: ctx.accounts.funder.to_account_info(),
-                },
-            ),
-            amount,
-            ctx.accounts.rewards_mint.decimals,
-        )?;
-
-        Ok(())
-    }
-
-
-    pub fn claim_rewards(ctx: Context<ClaimRewards>, args: ClaimRewardsArgs) -> Result<()> {
-        let leaf = solana_program::keccak::hashv(&[
-            ctx.accounts.owner.key().as_ref(),
-            &args.total_rewards.to_le_bytes(),
-        ]);
-        let computed_root = recompute(leaf.to_bytes(), &args.proof, args.index);
+    // permissionless crank, records how an operator did on a closed proposal.
+    // proposals are recorded in order so none is skipped or counted twice
+    pub fn record_missed_votes(ctx: Context<RecordMissedVotes>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let voter_state = &mut ctx.accounts.voter_state;
 
+        require_eq!(
+            proposal.id, voter_state.recorded_proposals,
+            MiniNcnError::ProposalOutOfOrder
+        );
+        // a proposal nobody finalized is still closed once its window ends
         require!(
-            computed_root == ctx.accounts.ballot_box.rewards_root,
-            MiniNcnError::InvalidProof
+            proposal.status != ProposalStatus::Voting
+                || Clock::get()?.epoch > proposal.end_epoch,
+            MiniNcnError::ProposalNotFinalized
         );
+        voter_state.recorded_proposals += 1;
 
-        let rewards_state = &mut ctx.accounts.rewards_state;
+        // operators outside the snapshot could not vote
+        let operator_snapshot = &ctx.accounts.operator_snapshot;
+        if operator_snapshot.data_is_empty() {
+            return Ok(());
+        }
+        let operator_snapshot =
+            OperatorSnapshot::try_deserialize(&mut &operator_snapshot.try_borrow_data()?[..])?;
+        voter_state.eligible_stake_weight += operator_snapshot.stake_weight;
+
+        let vote_record = &ctx.accounts.vote_record;
+        if vote_record.data_is_empty() {
+            voter_state.missed_votes += 1;
+            if voter_state.consecutive_missed_epochs == 0
+                || proposal.end_epoch > voter_state.last_missed_epoch
+            {
+                voter_state.consecutive_missed_epochs += 1;
+            }
+            voter_state.last_missed_epoch = proposal.end_epoch;
+            return Ok(());
+        }
 
-        // transfer unclaimed rewards tokens
-        let unclaimed_rewards = args.total_rewards - rewards_state.claimed_rewards;
-        require!(unclaimed_rewards > 0, MiniNcnError::AlreadyClaimed);
+        let vote_record = VoteRecord::try_deserialize(&mut &vote_record.try_borrow_data()?[..])?;
+        voter_state.consecutive_missed_epochs = 0;
+        voter_state.participated_stake_weight += vote_record.stake_weight;
+        if let (VoteChoice::Approve { ballot }, Some(winning_ballot)) =
+            (vote_record.choice, ctx.accounts.ballot_box.winning_ballot)
+        {
+            if ballot == winning_ballot {
+                voter_state.votes_agreeing += 1;
+            }
+        }
 
-        anchor_spl::token_interface::transfer_checked(
-            CpiContext::new_with_signer(
-                ctx.accounts.rewards_token_program.to_account_info(),
-                anchor_spl::token_interface::TransferChecked {
+        Ok(())
+    }
+
+    // the NCN slashes through its ncn_admin, which both programs accept as a slasher
+    pub fn register_slasher(
+        ctx: Context<RegisterSlasher>,
+        max_slashable_per_epoch: u64,
+    ) -> Result<()> {
+        jito_restaking_client::instructions::InitializeNcnVaultSlasherTicketCpi::new(
+            &ctx.accounts.jito_restaking_program,
+            jito_restaking_client::instructions::InitializeNcnVaultSlasherTicketCpiAccounts {
+                config: &ctx.accounts.jito_restaking_config.to_account_info(),
+                ncn: &ctx.accounts.ncn.to_account_info(),
+                vault: &ctx.accounts.vault.to_account_info(),
+                slasher: &ctx.accounts.ncn_admin.to_account_info(),
+                ncn_vault_ticket: &ctx.accounts.ncn_vault_ticket.to_account_info(),
+                ncn_vault_slasher_ticket: &ctx.accounts.ncn_vault_slasher_ticket.to_account_info(),
+                admin: &ctx.accounts.ncn_admin.to_account_info(),
+                payer: &ctx.accounts.payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+            },
+            jito_restaking_client::instructions::InitializeNcnVaultSlasherTicketInstructionArgs {
+                args: max_slashable_per_epoch,
+            },
+        )
+        .invoke_signed(&[&[
+            pda::NCN_ADMIN_SEED,
+            ctx.accounts.ncn.key().as_ref(),
+            &[ctx.bumps.ncn_admin],
+        ]])?;
+
+        // the vault side is signed by the vault slasher_admin, which is our vault_admin for our own vault
+        let (_, vault_admin_bump) = pda::find_vault_admin_address(&ctx.accounts.vault.key());
+        jito_vault_client::instructions::InitializeVaultNcnSlasherTicketCpi::new(
+            &ctx.accounts.jito_vault_program,
+            jito_vault_client::instructions::InitializeVaultNcnSlasherTicketCpiAccounts {
+                config: &ctx.accounts.jito_vault_config.to_account_info(),
+                vault: &ctx.accounts.vault.to_account_info(),
+                ncn: &ctx.accounts.ncn.to_account_info(),
+                slasher: &ctx.accounts.ncn_admin.to_account_info(),
+                ncn_slasher_ticket: &ctx.accounts.ncn_vault_slasher_ticket.to_account_info(),
+                vault_slasher_ticket: &ctx.accounts.vault_ncn_slasher_ticket.to_account_info(),
+                admin: &ctx.accounts.vault_slasher_admin.to_account_info(),
+                payer: &ctx.accounts.payer.to_account_info(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+            },
+        )
+        .invoke_signed(&[&[
+            pda::VAULT_ADMIN_SEED,
+            ctx.accounts.vault.key().as_ref(),
+            &[vault_admin_bump],
+        ]])?;
+
+        Ok(())
+    }
+
+    pub fn warmup_slasher(ctx: Context<WarmupSlasher>) -> Result<()> {
+        jito_restaking_client::instructions::WarmupNcnVaultSlasherTicketCpi::new(
+            &ctx.accounts.jito_restaking_program,
+            jito_restaking_client::instructions::WarmupNcnVaultSlasherTicketCpiAccounts {
+                config: &ctx.accounts.jito_restaking_config.to_account_info(),
+                ncn: &ctx.accounts.ncn.to_account_info(),
+                vault: &ctx.accounts.vault.to_account_info(),
+                slasher: &ctx.accounts.ncn_admin.to_account_info(),
+                ncn_vault_ticket: &ctx.accounts.ncn_vault_ticket.to_account_info(),
+                ncn_vault_slasher_ticket: &ctx.accounts.ncn_vault_slasher_ticket.to_account_info(),
+                admin: &ctx.accounts.ncn_admin.to_account_info(),
+            },
+        )
+        .invoke_signed(&[&[
+            pda::NCN_ADMIN_SEED,
+            ctx.accounts.ncn.key().as_ref(),
+            &[ctx.bumps.ncn_admin],
+        ]])?;
+
+        let (_, vault_admin_bump) = pda::find_vault_admin_address(&ctx.accounts.vault.key());
+        jito_vault_client::instructions::WarmupVaultNcnSlasherTicketCpi::new(
+            &ctx.accounts.jito_vault_program,
+            jito_vault_client::instructions::WarmupVaultNcnSlasherTicketCpiAccounts {
+                config: &ctx.accounts.jito_vault_config.to_account_info(),
+                vault: &ctx.accounts.vault.to_account_info(),
+                ncn: &ctx.accounts.ncn.to_account_info(),
+                slasher: &ctx.accounts.ncn_admin.to_account_info(),
+                vault_slasher_ticket: &ctx.accounts.vault_ncn_slasher_ticket.to_account_info(),
+                admin: &ctx.accounts.vault_slasher_admin.to_account_info(),
+            },
+        )
+        .invoke_signed(&[&[
+            pda::VAULT_ADMIN_SEED,
+            ctx.accounts.vault.key().as_ref(),
+            &[vault_admin_bump],
+        ]])?;
+
+        Ok(())
+    }
+
+    // penalize an operator that voted against the outcome of a finalized proposal, or did not vote at all.
+    // the vault program has no slash instruction, so the penalty is a forced undelegation:
+    // `amount` of the operator's delegation cools down and returns to the vault, nothing is burned.
+    // for the same reason the slasher operator ticket's `slashed` is never written, only the vault
+    // program can and it has no instruction that does. this program records each undelegation in an
+    // `UndelegationRecord` instead, which takes the place of a slash record.
+    // without a passed undelegation proposal, only the authority can force it
+    pub fn force_undelegate(ctx: Context<ForceUndelegate>, amount: u64) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let operator = ctx.accounts.operator.key();
+        require!(amount > 0, MiniNcnError::InvalidUndelegationAmount);
+
+        match &ctx.accounts.undelegation_proposal {
+            Some(undelegation_proposal) => {
+                require_keys_eq!(
+                    undelegation_proposal.config, ctx.accounts.config.key(),
+                    MiniNcnError::ConfigMismatch
+                );
+                require!(
+                    undelegation_proposal.status == ProposalStatus::Approved,
+                    MiniNcnError::UndelegationNotApproved
+                );
+                require!(
+                    undelegation_proposal.kind
+                        == ProposalKind::ForceUndelegation {
+                            proposal: proposal.key(),
+                            operator,
+                            amount,
+                        },
+                    MiniNcnError::UndelegationNotApproved
+                );
+            }
+            // checked against config.authority by the account constraint
+            None => require!(
+                ctx.accounts.authority.is_some(),
+                MiniNcnError::InvalidAuthority
+            ),
+        }
+
+        require!(
+            proposal.status != ProposalStatus::Voting,
+            MiniNcnError::ProposalNotFinalized
+        );
+        let winning_ballot = ctx
+            .accounts
+            .ballot_box
+            .winning_ballot
+            .ok_or(MiniNcnError::ProposalNotFinalized)?;
+
+        let vote_record = &ctx.accounts.vote_record;
+        let voted = if vote_record.data_is_empty() {
+            false
+        } else {
+            let vote_record = VoteRecord::try_deserialize(&mut &vote_record.try_borrow_data()?[..])?;
+            require!(
+                match vote_record.choice {
+                    VoteChoice::Approve { ballot } => ballot != winning_ballot,
+                    VoteChoice::Reject => true,
+                    VoteChoice::Abstain => false,
+                },
+                MiniNcnError::OperatorVotedForOutcome
+            );
+            true
+        };
+
+        // the cap of the slasher ticket holds per vault epoch, across all proposals
+        let (epoch, max_per_epoch) = {
+            let jito_vault_config: Ref<jito_vault_client::zero_copy::ConfigRef> =
+                vault_account_ref(&ctx.accounts.jito_vault_config)?;
+            let vault_ncn_slasher_ticket: Ref<VaultNcnSlasherTicketRef> =
+                vault_account_ref(&ctx.accounts.vault_ncn_slasher_ticket)?;
+            let epoch_length = u64::from(jito_vault_config.epoch_length);
+            let epoch = Clock::get()?
+                .slot
+                .checked_div(epoch_length)
+                .ok_or(MiniNcnError::InvalidEpochLength)?;
+
+            (epoch, u64::from(vault_ncn_slasher_ticket.max_slashable_per_epoch))
+        };
+
+        // the operator ticket is seeded by the vault epoch, so it is only known here
+        require_keys_eq!(
+            ctx.accounts.vault_ncn_slasher_operator_ticket.key(),
+            vault_pda::find_vault_ncn_slasher_operator_ticket_address(
+                &ctx.accounts.vault.key(),
+                &ctx.accounts.ncn.key(),
+                &ctx.accounts.ncn_admin.key(),
+                &operator,
+                epoch,
+            )
+            .0,
+            MiniNcnError::InvalidSlasherOperatorTicket
+        );
+
+        let undelegation_tally = &mut ctx.accounts.undelegation_tally;
+        undelegation_tally.vault = ctx.accounts.vault.key();
+        undelegation_tally.operator = operator;
+        if undelegation_tally.epoch != epoch {
+            undelegation_tally.epoch = epoch;
+            undelegation_tally.amount = 0;
+        }
+        undelegation_tally.amount = undelegation_tally
+            .amount
+            .checked_add(amount)
+            .filter(|total| *total <= max_per_epoch)
+            .ok_or(MiniNcnError::UndelegationCapExceeded)?;
+
+        // the vault tracks the slasher and operator of each epoch
+        if ctx.accounts.vault_ncn_slasher_operator_ticket.data_is_empty() {
+            jito_vault_client::instructions::InitializeVaultNcnSlasherOperatorTicketCpi::new(
+                &ctx.accounts.jito_vault_program,
+                jito_vault_client::instructions::InitializeVaultNcnSlasherOperatorTicketCpiAccounts {
+                    config: &ctx.accounts.jito_vault_config.to_account_info(),
+                    vault: &ctx.accounts.vault.to_account_info(),
+                    ncn: &ctx.accounts.ncn.to_account_info(),
+                    slasher: &ctx.accounts.ncn_admin.to_account_info(),
+                    operator: &ctx.accounts.operator.to_account_info(),
+                    vault_ncn_slasher_ticket: &ctx.accounts.vault_ncn_slasher_ticket.to_account_info(),
+                    vault_ncn_slasher_operator_ticket: &ctx
+                        .accounts
+                        .vault_ncn_slasher_operator_ticket
+                        .to_account_info(),
+                    payer: &ctx.accounts.payer.to_account_info(),
+                    system_program: &ctx.accounts.system_program.to_account_info(),
+                },
+            )
+            .invoke()?;
+        }
+
+        // the stake stays in the vault, the operator just stops earning on it
+        jito_vault_client::instructions::CooldownDelegationCpi::new(
+            &ctx.accounts.jito_vault_program,
+            jito_vault_client::instructions::CooldownDelegationCpiAccounts {
+                config: &ctx.accounts.jito_vault_config.to_account_info(),
+                vault: &ctx.accounts.vault.to_account_info(),
+                operator: &ctx.accounts.operator.to_account_info(),
+                vault_operator_delegation: &ctx.accounts.vault_operator_delegation.to_account_info(),
+                admin: &ctx.accounts.vault_admin.to_account_info(),
+            },
+            jito_vault_client::instructions::CooldownDelegationInstructionArgs { amount },
+        )
+        .invoke_signed(&[&[
+            pda::VAULT_ADMIN_SEED,
+            ctx.accounts.vault.key().as_ref(),
+            &[ctx.bumps.vault_admin],
+        ]])?;
+
+        let undelegation_record = &mut ctx.accounts.undelegation_record;
+        undelegation_record.proposal = proposal.key();
+        undelegation_record.operator = operator;
+        undelegation_record.vault = ctx.accounts.vault.key();
+        undelegation_record.epoch = epoch;
+        undelegation_record.amount = amount;
+        undelegation_record.voted = voted;
+
+        Ok(())
+    }
+
+    // rewards are held in an ATA of ncn_admin per registered mint
+    pub fn add_rewards_mint(ctx: Context<AddRewardsMint>) -> Result<()> {
+        let rewards_vault = &mut ctx.accounts.rewards_vault;
+        rewards_vault.config = ctx.accounts.config.key();
+        rewards_vault.rewards_mint = ctx.accounts.rewards_mint.key();
+        rewards_vault.token_program = ctx.accounts.rewards_token_program.key();
+
+        Ok(())
+    }
+
+    // one distribution per epoch and mint, paying out the root of an approved rewards proposal.
+    // a root can hold leaves of several mints, each leaf commits to its mint
+    pub fn initialize_distribution(
+        ctx: Context<InitializeDistribution>,
+        total_amount: u64,
+        expiry_epoch: u64,
+    ) -> Result<()> {
+        ctx.accounts.distribution.initialize(
+            ctx.accounts.config.key(),
+            &ctx.accounts.proposal,
+            &ctx.accounts.ballot_box,
+            ctx.accounts.rewards_mint.key(),
+            total_amount,
+            expiry_epoch,
+        )
+    }
+
+    // lamports are held by the rewards_sol PDA of the config
+    pub fn initialize_sol_distribution(
+        ctx: Context<InitializeSolDistribution>,
+        total_amount: u64,
+        expiry_epoch: u64,
+    ) -> Result<()> {
+        ctx.accounts.rewards_sol.config = ctx.accounts.config.key();
+
+        ctx.accounts.distribution.initialize(
+            ctx.accounts.config.key(),
+            &ctx.accounts.proposal,
+            &ctx.accounts.ballot_box,
+            NATIVE_SOL_MINT,
+            total_amount,
+            expiry_epoch,
+        )
+    }
+
+    // large distributions track claims in one bitmap instead of a RewardsState per claimant
+    pub fn initialize_claim_bitmap(
+        ctx: Context<InitializeClaimBitmap>,
+        leaf_count: u32,
+    ) -> Result<()> {
+        require!(
+            leaf_count > 0 && leaf_count <= MAX_CLAIM_BITMAP_LEAVES,
+            MiniNcnError::InvalidLeafCount
+        );
+
+        // claims through RewardsState would not show up in the bitmap
+        let distribution = &mut ctx.accounts.distribution;
+        require!(
+            distribution.claimed_amount == 0,
+            MiniNcnError::DistributionAlreadyClaimed
+        );
+        distribution.has_claim_bitmap = true;
+
+        let claim_bitmap = &mut ctx.accounts.claim_bitmap;
+        claim_bitmap.distribution = distribution.key();
+        claim_bitmap.leaf_count = leaf_count;
+        claim_bitmap.bits = vec![0; ClaimBitmap::bits_len(leaf_count)];
+
+        Ok(())
+    }
+
+    pub fn close_claim_bitmap(ctx: Context<CloseClaimBitmap>) -> Result<()> {
+        require!(
+            Clock::get()?.epoch > ctx.accounts.distribution.expiry_epoch,
+            MiniNcnError::DistributionNotExpired
+        );
+
+        Ok(())
+    }
+
+    // sweeps what is left of an expired distribution to the treasury
+    pub fn clawback(ctx: Context<Clawback>) -> Result<()> {
+        let distribution = &mut ctx.accounts.distribution;
+        let amount = distribution.record_clawback()?;
+
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.rewards_token_program.to_account_info(),
+                anchor_spl::token_interface::TransferChecked {
                     mint: ctx.accounts.rewards_mint.to_account_info(),
                     from: ctx.accounts.rewards_token_account.to_account_info(),
-                    to: ctx.accounts.beneficiary_token_account.to_account_info(),
+                    to: ctx.accounts.treasury_token_account.to_account_info(),
                     authority: ctx.accounts.ncn_admin.to_account_info(),
                 },
                 &[&[
-                    b"ncn_admin",
+                    pda::NCN_ADMIN_SEED,
                     ctx.accounts.config.ncn.as_ref(),
                     &[ctx.bumps.ncn_admin],
                 ]],
             ),
+            amount,
+            ctx.accounts.rewards_mint.decimals,
+        )?;
+
+        emit!(RewardsClawedBack {
+            distribution: distribution.key(),
+            rewards_mint: distribution.rewards_mint,
+            treasury: ctx.accounts.config.treasury,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    // same as clawback, for lamports held by rewards_sol
+    pub fn clawback_sol(ctx: Context<ClawbackSol>) -> Result<()> {
+        let distribution = &mut ctx.accounts.distribution;
+        let amount = distribution.record_clawback()?;
+
+        let rewards_sol = ctx.accounts.rewards_sol.to_account_info();
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(rewards_sol.data_len());
+        require!(
+            rewards_sol.lamports() >= rent_exempt_minimum + amount,
+            MiniNcnError::InsufficientRewardsSol
+        );
+        rewards_sol.sub_lamports(amount)?;
+        ctx.accounts.treasury.add_lamports(amount)?;
+
+        emit!(RewardsClawedBack {
+            distribution: distribution.key(),
+            rewards_mint: distribution.rewards_mint,
+            treasury: ctx.accounts.config.treasury,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        ctx.accounts.distribution.record_funded(amount)?;
+
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.rewards_token_program.to_account_info(),
+                anchor_spl::token_interface::TransferChecked {
+                    mint: ctx.accounts.rewards_mint.to_account_info(),
+                    from: ctx.accounts.fund_token_account.to_account_info(),
+                    to: ctx.accounts.rewards_token_account.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.rewards_mint.decimals,
+        )?;
+
+        Ok(())
+    }
+
+    pub fn fund_rewards_sol(ctx: Context<FundRewardsSol>, amount: u64) -> Result<()> {
+        ctx.accounts.distribution.record_funded(amount)?;
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.rewards_sol.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        Ok(())
+    }
+
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, args: ClaimRewardsArgs) -> Result<()> {
+        // transfer unclaimed rewards tokens
+        let unclaimed_rewards = record_claim(
+            &mut ctx.accounts.distribution,
+            &mut ctx.accounts.rewards_state,
+            ctx.accounts.owner.key(),
+            &args,
+        )?;
+
+        transfer_rewards(
+            &ctx.accounts.rewards_token_program,
+            &ctx.accounts.rewards_mint,
+            &ctx.accounts.rewards_token_account,
+            &ctx.accounts.beneficiary_token_account,
+            &ctx.accounts.ncn_admin,
+            &[&[
+                pda::NCN_ADMIN_SEED,
+                ctx.accounts.config.ncn.as_ref(),
+                &[ctx.bumps.ncn_admin],
+            ]],
             unclaimed_rewards,
+        )
+    }
+
+    pub fn claim_rewards_sol(ctx: Context<ClaimRewardsSol>, args: ClaimRewardsArgs) -> Result<()> {
+        let unclaimed_rewards = record_claim(
+            &mut ctx.accounts.distribution,
+            &mut ctx.accounts.rewards_state,
+            ctx.accounts.owner.key(),
+            &args,
+        )?;
+
+        // rewards_sol is owned by this program, so lamports are moved directly.
+        // it must keep its rent exempt minimum whatever the distributions account for
+        let rewards_sol = ctx.accounts.rewards_sol.to_account_info();
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(rewards_sol.data_len());
+        require!(
+            rewards_sol.lamports() >= rent_exempt_minimum + unclaimed_rewards,
+            MiniNcnError::InsufficientRewardsSol
+        );
+        rewards_sol.sub_lamports(unclaimed_rewards)?;
+        ctx.accounts.beneficiary.add_lamports(unclaimed_rewards)?;
+
+        Ok(())
+    }
+
+    // funds an approved StakeRewards proposal, split later by split_stake_rewards
+    pub fn initialize_stake_distribution(ctx: Context<InitializeStakeDistribution>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let ProposalKind::StakeRewards {
+            rewards_mint,
+            amount,
+        } = proposal.kind
+        else {
+            return err!(MiniNcnError::InvalidProposal);
+        };
+        require!(
+            proposal.status == ProposalStatus::Approved,
+            MiniNcnError::InvalidProposal
+        );
+        require_keys_eq!(
+            rewards_mint,
+            ctx.accounts.rewards_mint.key(),
+            MiniNcnError::InvalidRewardsMint
+        );
+
+        let epoch_snapshot = &ctx.accounts.epoch_snapshot;
+        require!(epoch_snapshot.is_finalized(), MiniNcnError::EpochSnapshotNotFinalized);
+
+        let stake_distribution = &mut ctx.accounts.stake_distribution;
+        stake_distribution.config = ctx.accounts.config.key();
+        stake_distribution.proposal = proposal.key();
+        stake_distribution.epoch = proposal.snapshot_epoch;
+        stake_distribution.rewards_mint = rewards_mint;
+        stake_distribution.total_amount = amount;
+        stake_distribution.total_stake_weight = epoch_snapshot.total_stake_weight;
+        stake_distribution.operators_split = 0;
+        stake_distribution.operator_fees = 0;
+        stake_distribution.vault_rewards = 0;
+
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.rewards_token_program.to_account_info(),
+                anchor_spl::token_interface::TransferChecked {
+                    mint: ctx.accounts.rewards_mint.to_account_info(),
+                    from: ctx.accounts.fund_token_account.to_account_info(),
+                    to: ctx.accounts.rewards_token_account.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                },
+            ),
+            amount,
             ctx.accounts.rewards_mint.decimals,
         )?;
 
-        rewards_state.owner = ctx.accounts.owner.key();
-        rewards_state.claimed_rewards = args.total_rewards;
+        Ok(())
+    }
+
+    // permissionless crank, once per operator and vault of the snapshot. the operator
+    // keeps operator_fee_bps of the share the vault delegated to it, the rest goes to
+    // that vault and reaches its VRT holders through update_vault_balance, like
+    // route_rewards. vaults holding another token can not take the rewards mint, their
+    // share stays with ncn_admin, as does rounding dust
+    pub fn split_stake_rewards(ctx: Context<SplitStakeRewards>) -> Result<()> {
+        let operator_fee_bps = {
+            let operator: Ref<OperatorRef> = restaking_account_ref(&ctx.accounts.operator)?;
+            require_keys_eq!(
+                operator.admin,
+                ctx.accounts.operator_fee_token_account.owner,
+                MiniNcnError::InvalidBeneficiary
+            );
+            u16::from(operator.operator_fee_bps)
+        };
+
+        {
+            let vault: Ref<VaultRef> = vault_account_ref(&ctx.accounts.vault)?;
+            require_keys_eq!(
+                vault.supported_mint,
+                ctx.accounts.rewards_mint.key(),
+                MiniNcnError::InvalidRewardsMint
+            );
+        }
+
+        // only the stake this vault delegated earns it rewards
+        let vault_index = ctx.accounts.vault_entry.index;
+        require!(
+            vault_index < ctx.accounts.epoch_snapshot.vault_count,
+            MiniNcnError::VaultNotInSnapshot
+        );
+        let stake_weight = ctx.accounts.operator_snapshot.vault_stake_weights[vault_index as usize];
+
+        let stake_distribution = &mut ctx.accounts.stake_distribution;
+        let (operator_fee, vault_rewards) =
+            stake_distribution.split(stake_weight, operator_fee_bps)?;
+
+        stake_distribution.operators_split += 1;
+        stake_distribution.operator_fees += operator_fee;
+        stake_distribution.vault_rewards += vault_rewards;
+
+        let operator_rewards = &mut ctx.accounts.operator_rewards;
+        operator_rewards.stake_distribution = stake_distribution.key();
+        operator_rewards.operator = ctx.accounts.operator.key();
+        operator_rewards.vault = ctx.accounts.vault.key();
+        operator_rewards.stake_weight = stake_weight;
+        operator_rewards.operator_fee = operator_fee;
+        operator_rewards.vault_rewards = vault_rewards;
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            pda::NCN_ADMIN_SEED,
+            ctx.accounts.config.ncn.as_ref(),
+            &[ctx.bumps.ncn_admin],
+        ]];
+        for (to, amount) in [
+            (&ctx.accounts.operator_fee_token_account, operator_fee),
+            (&ctx.accounts.vault_token_account, vault_rewards),
+        ] {
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.rewards_token_program.to_account_info(),
+                    anchor_spl::token_interface::TransferChecked {
+                        mint: ctx.accounts.rewards_mint.to_account_info(),
+                        from: ctx.accounts.rewards_token_account.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: ctx.accounts.ncn_admin.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+                ctx.accounts.rewards_mint.decimals,
+            )?;
+        }
+
+        jito_vault_client::instructions::UpdateVaultBalanceCpi::new(
+            &ctx.accounts.jito_vault_program,
+            jito_vault_client::instructions::UpdateVaultBalanceCpiAccounts {
+                config: &ctx.accounts.jito_vault_config.to_account_info(),
+                vault: &ctx.accounts.vault.to_account_info(),
+                vault_token_account: &ctx.accounts.vault_token_account.to_account_info(),
+                vrt_mint: &ctx.accounts.vrt_mint.to_account_info(),
+                vault_fee_token_account: &ctx.accounts.vault_fee_token_account.to_account_info(),
+                token_program: &ctx.accounts.vrt_token_program.to_account_info(),
+            },
+        )
+        .invoke()?;
+
+        Ok(())
+    }
+
+    // pays the gross reward of an operator the way Jito advertises it: operator_fee_bps
+    // to the operator, the rest to the vault, where update_vault_balance takes the vault
+    // and program fees as VRT. Jito operators have no fee wallet, so the fee goes to a
+    // token account owned by the operator admin.
+    // paid from the funder like fund_rewards, the ncn_admin ATAs back the distributions
+    pub fn route_rewards(ctx: Context<RouteRewards>, gross_amount: u64) -> Result<()> {
+        let operator_fee_bps = {
+            let operator: Ref<OperatorRef> = restaking_account_ref(&ctx.accounts.operator)?;
+            require_keys_eq!(
+                operator.admin,
+                ctx.accounts.operator_fee_token_account.owner,
+                MiniNcnError::InvalidBeneficiary
+            );
+            u16::from(operator.operator_fee_bps)
+        };
+
+        {
+            let vault_operator_delegation: Ref<VaultOperatorDelegationRef> =
+                vault_account_ref(&ctx.accounts.vault_operator_delegation)?;
+            require_keys_eq!(
+                vault_operator_delegation.vault,
+                ctx.accounts.vault.key(),
+                MiniNcnError::InvalidVault
+            );
+        }
+
+        // copied out, the vault is passed to update_vault_balance below
+        let (reward_fee_bps, program_fee_bps) = {
+            let vault: Ref<VaultRef> = vault_account_ref(&ctx.accounts.vault)?;
+            // rewards only reach VRT holders in the token the vault holds
+            require_keys_eq!(
+                vault.supported_mint,
+                ctx.accounts.rewards_mint.key(),
+                MiniNcnError::InvalidRewardsMint
+            );
+            (u16::from(vault.reward_fee_bps), u16::from(vault.program_fee_bps))
+        };
+
+        let (operator_fee, vault_rewards) = split_fee(gross_amount, operator_fee_bps)?;
+        // informational, the vault program charges these by minting VRT
+        let (vault_fee, _) = split_fee(vault_rewards, reward_fee_bps)?;
+        let (program_fee, _) = split_fee(vault_rewards, program_fee_bps)?;
+
+        for (to, amount) in [
+            (&ctx.accounts.operator_fee_token_account, operator_fee),
+            (&ctx.accounts.vault_token_account, vault_rewards),
+        ] {
+            anchor_spl::token_interface::transfer_checked(
+                CpiContext::new(
+                    ctx.accounts.rewards_token_program.to_account_info(),
+                    anchor_spl::token_interface::TransferChecked {
+                        mint: ctx.accounts.rewards_mint.to_account_info(),
+                        from: ctx.accounts.fund_token_account.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: ctx.accounts.funder.to_account_info(),
+                    },
+                ),
+                amount,
+                ctx.accounts.rewards_mint.decimals,
+            )?;
+        }
+
+        jito_vault_client::instructions::UpdateVaultBalanceCpi::new(
+            &ctx.accounts.jito_vault_program,
+            jito_vault_client::instructions::UpdateVaultBalanceCpiAccounts {
+                config: &ctx.accounts.jito_vault_config.to_account_info(),
+                vault: &ctx.accounts.vault.to_account_info(),
+                vault_token_account: &ctx.accounts.vault_token_account.to_account_info(),
+                vrt_mint: &ctx.accounts.vrt_mint.to_account_info(),
+                vault_fee_token_account: &ctx.accounts.vault_fee_token_account.to_account_info(),
+                token_program: &ctx.accounts.vrt_token_program.to_account_info(),
+            },
+        )
+        .invoke()?;
+
+        emit!(RewardsRouted {
+            operator: ctx.accounts.operator.key(),
+            vault: ctx.accounts.vault.key(),
+            rewards_mint: ctx.accounts.rewards_mint.key(),
+            gross_amount,
+            operator_fee,
+            vault_rewards,
+            vault_fee,
+            program_fee,
+        });
+
+        Ok(())
+    }
+
+    // lets anyone claim for the owner, paying to the registered beneficiary
+    pub fn set_claim_delegate(ctx: Context<SetClaimDelegate>, beneficiary: Pubkey) -> Result<()> {
+        let claim_delegate = &mut ctx.accounts.claim_delegate;
+        claim_delegate.config = ctx.accounts.config.key();
+        claim_delegate.owner = ctx.accounts.owner.key();
+        claim_delegate.beneficiary = beneficiary;
 
         Ok(())
     }
+
+    // permissionless claim, tokens go to the ATA of the owner or of its registered beneficiary
+    pub fn claim_rewards_for(ctx: Context<ClaimRewardsFor>, args: ClaimRewardsArgs) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        check_claim_for_beneficiary(
+            &owner,
+            &ctx.accounts.claim_delegate,
+            &ctx.accounts.beneficiary_token_account,
+            &ctx.accounts.rewards_token_program.key(),
+        )?;
+
+        let unclaimed_rewards = record_claim(
+            &mut ctx.accounts.distribution,
+            &mut ctx.accounts.rewards_state,
+            owner,
+            &args,
+        )?;
+
+        transfer_rewards(
+            &ctx.accounts.rewards_token_program,
+            &ctx.accounts.rewards_mint,
+            &ctx.accounts.rewards_token_account,
+            &ctx.accounts.beneficiary_token_account,
+            &ctx.accounts.ncn_admin,
+            &[&[
+                pda::NCN_ADMIN_SEED,
+                ctx.accounts.config.ncn.as_ref(),
+                &[ctx.bumps.ncn_admin],
+            ]],
+            unclaimed_rewards,
+        )
+    }
+
+    pub fn claim_rewards_with_bitmap(
+        ctx: Context<ClaimRewardsWithBitmap>,
+        args: ClaimRewardsArgs,
+    ) -> Result<()> {
+        let total_rewards = record_bitmap_claim(
+            &mut ctx.accounts.distribution,
+            &mut ctx.accounts.claim_bitmap,
+            ctx.accounts.owner.key(),
+            &args,
+        )?;
+
+        transfer_rewards(
+            &ctx.accounts.rewards_token_program,
+            &ctx.accounts.rewards_mint,
+            &ctx.accounts.rewards_token_account,
+            &ctx.accounts.beneficiary_token_account,
+            &ctx.accounts.ncn_admin,
+            &[&[
+                pda::NCN_ADMIN_SEED,
+                ctx.accounts.config.ncn.as_ref(),
+                &[ctx.bumps.ncn_admin],
+            ]],
+            total_rewards,
+        )
+    }
+
+    // claim_rewards_for, for distributions with a claim bitmap
+    pub fn claim_rewards_with_bitmap_for(
+        ctx: Context<ClaimRewardsWithBitmapFor>,
+        args: ClaimRewardsArgs,
+    ) -> Result<()> {
+        let owner = ctx.accounts.owner.key();
+        check_claim_for_beneficiary(
+            &owner,
+            &ctx.accounts.claim_delegate,
+            &ctx.accounts.beneficiary_token_account,
+            &ctx.accounts.rewards_token_program.key(),
+        )?;
+
+        let total_rewards = record_bitmap_claim(
+            &mut ctx.accounts.distribution,
+            &mut ctx.accounts.claim_bitmap,
+            owner,
+            &args,
+        )?;
+
+        transfer_rewards(
+            &ctx.accounts.rewards_token_program,
+            &ctx.accounts.rewards_mint,
+            &ctx.accounts.rewards_token_account,
+            &ctx.accounts.beneficiary_token_account,
+            &ctx.accounts.ncn_admin,
+            &[&[
+                pda::NCN_ADMIN_SEED,
+                ctx.accounts.config.ncn.as_ref(),
+                &[ctx.bumps.ncn_admin],
+            ]],
+            total_rewards,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct InitializeNcn<'info> {
+    /// CHECK:
+    #[account(mut, seeds = [restaking_pda::CONFIG_SEED], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub jito_restaking_config: UncheckedAccount<'info>,
+    pub base: Signer<'info>,
+    #[account(mut, seeds = [restaking_pda::NCN_SEED, base.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub ncn: SystemAccount<'info>,
+    #[account(init, payer = payer,
+        space = Config::DISCRIMINATOR.len() + Config::INIT_SPACE,
+        seeds = [pda::CONFIG_SEED, ncn.key().as_ref()], bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut, seeds = [pda::NCN_ADMIN_SEED, ncn.key().as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK:
+    #[account(address = JITO_RESTAKING_ID)]
+    pub jito_restaking_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetConsensusThreshold<'info> {
+    #[account(mut, seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(mut, seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(mut, seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    #[account(constraint = config.pending_authority == Some(pending_authority.key()) @ MiniNcnError::InvalidAuthority)]
+    pub pending_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetRole<'info> {
+    #[account(mut, seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(mut, seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    #[account(constraint = config.is_pauser(&authority.key()) @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    #[account(mut, seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK:
+    #[account(address = JITO_VAULT_ID)]
+    pub jito_vault_program: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [vault_pda::CONFIG_SEED], bump, seeds::program = JITO_VAULT_ID)]
+    pub jito_vault_config: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [vault_pda::VAULT_SEED, config.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
+    pub vault: SystemAccount<'info>,
+    #[account()]
+    pub st_mint: Account<'info, anchor_spl::token::Mint>,
+    #[account(mut, associated_token::mint = st_mint, associated_token::authority = vault_admin)]
+    pub admin_st_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+    #[account(mut, associated_token::mint = st_mint, associated_token::authority = vault)]
+    pub vault_st_token_account: Account<'info, anchor_spl::token::TokenAccount>,
+    /// CHECK:
+    #[account(mut, seeds = [pda::VRT_MINT_SEED, st_mint.key().as_ref()], bump)]
+    pub vrt_mint: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [vault_pda::BURN_VAULT_SEED, config.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
+    pub burn_vault: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, address = anchor_spl::associated_token::get_associated_token_address_with_program_id(burn_vault.key, vrt_mint.key, token_program.key))]
+    pub burn_vault_vrt_token_account: UncheckedAccount<'info>,
+    #[account(mut, seeds = [pda::VAULT_ADMIN_SEED, vault.key().as_ref()], bump)]
+    pub vault_admin: SystemAccount<'info>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, anchor_spl::token::Token>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+}
+
+#[derive(Accounts)]
+pub struct SetWeight<'info> {
+    #[account(seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    pub st_mint: InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
+    #[account(init_if_needed, payer = payer,
+        space = WeightTable::DISCRIMINATOR.len() + WeightTable::INIT_SPACE,
+        seeds = [pda::WEIGHT_TABLE_SEED, config.key().as_ref(), st_mint.key().as_ref()], bump
+    )]
+    pub weight_table: Account<'info, WeightTable>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterVault<'info> {
+    #[account(mut, seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK:
+    #[account(seeds = [restaking_pda::CONFIG_SEED], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub jito_restaking_config: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [vault_pda::CONFIG_SEED], bump, seeds::program = JITO_VAULT_ID)]
+    pub jito_vault_config: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, address = config.ncn)]
+    pub ncn: UncheckedAccount<'info>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, ncn.key().as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    /// CHECK:
+    #[account(mut, owner = JITO_VAULT_ID)]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: the ncn_admin of the vault, checked by the vault program
+    pub vault_ncn_admin: UncheckedAccount<'info>,
+    #[account(has_one = config @ MiniNcnError::ConfigMismatch)]
+    pub weight_table: Account<'info, WeightTable>,
+    #[account(init, payer = payer,
+        space = VaultEntry::DISCRIMINATOR.len() + VaultEntry::INIT_SPACE,
+        seeds = [pda::VAULT_ENTRY_SEED, config.key().as_ref(), vault.key().as_ref()], bump
+    )]
+    pub vault_entry: Account<'info, VaultEntry>,
+    /// CHECK:
+    #[account(mut, seeds = [restaking_pda::NCN_VAULT_TICKET_SEED, ncn.key().as_ref(), vault.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub ncn_vault_ticket: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [vault_pda::VAULT_NCN_TICKET_SEED, vault.key().as_ref(), ncn.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
+    pub vault_ncn_ticket: UncheckedAccount<'info>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK:
+    #[account(address = JITO_RESTAKING_ID)]
+    pub jito_restaking_program: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(address = JITO_VAULT_ID)]
+    pub jito_vault_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WarmupVault<'info> {
+    #[account(seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK:
+    #[account(seeds = [restaking_pda::CONFIG_SEED], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub jito_restaking_config: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [vault_pda::CONFIG_SEED], bump, seeds::program = JITO_VAULT_ID)]
+    pub jito_vault_config: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(address = config.ncn)]
+    pub ncn: UncheckedAccount<'info>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, ncn.key().as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    #[account(seeds = [pda::VAULT_ENTRY_SEED, config.key().as_ref(), vault.key().as_ref()], bump)]
+    pub vault_entry: Account<'info, VaultEntry>,
+    /// CHECK:
+    #[account(address = vault_entry.vault)]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: the ncn_admin of the vault, checked by the vault program
+    pub vault_ncn_admin: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [restaking_pda::NCN_VAULT_TICKET_SEED, ncn.key().as_ref(), vault.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub ncn_vault_ticket: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [vault_pda::VAULT_NCN_TICKET_SEED, vault.key().as_ref(), ncn.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
+    pub vault_ncn_ticket: UncheckedAccount<'info>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    /// CHECK:
+    #[account(address = JITO_RESTAKING_ID)]
+    pub jito_restaking_program: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(address = JITO_VAULT_ID)]
+    pub jito_vault_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeOperator<'info> {
+    /// CHECK:
+    #[account(mut, seeds = [restaking_pda::CONFIG_SEED], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub jito_restaking_config: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [vault_pda::CONFIG_SEED], bump, seeds::program = JITO_VAULT_ID)]
+    pub jito_vault_config: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub config: Account<'info, Config>,
+    #[account(
+        init, payer = payer,
+        space = VoterState::DISCRIMINATOR.len() + VoterState::INIT_SPACE,
+        seeds = [pda::VOTER_STATE_SEED, config.key().as_ref(), operator.key().as_ref()], bump
+    )]
+    pub voter_state: Account<'info, VoterState>,
+    /// CHECK:
+    #[account(mut)]
+    pub operator: UncheckedAccount<'info>,
+    pub operator_admin: Signer<'info>,
+    /// CHECK:
+    #[account(mut, address = config.ncn)]
+    pub ncn: UncheckedAccount<'info>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, ncn.key().as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [restaking_pda::NCN_OPERATOR_STATE_SEED, ncn.key().as_ref(), operator.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub ncn_operator_state: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [vault_pda::VAULT_SEED, config.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut, seeds = [pda::VAULT_ADMIN_SEED, vault.key().as_ref()], bump)]
+    pub vault_admin: SystemAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [restaking_pda::OPERATOR_VAULT_TICKET_SEED, operator.key().as_ref(), vault.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub operator_vault_ticket: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [vault_pda::VAULT_OPERATOR_DELEGATION_SEED, vault.key().as_ref(), operator.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
+    pub vault_operator_delegation: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK:
+    #[account(address = JITO_RESTAKING_ID)]
+    pub jito_restaking_program: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(address = JITO_VAULT_ID)]
+    pub jito_vault_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WarmupOperator<'info> {
+    /// CHECK:
+    #[account(seeds = [restaking_pda::CONFIG_SEED], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub jito_restaking_config: UncheckedAccount<'info>,
+    #[account()]
+    pub config: Account<'info, Config>,
+    /// CHECK:
+    #[account(address = config.ncn)]
+    pub ncn: UncheckedAccount<'info>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, ncn.key().as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    #[account(seeds = [pda::VOTER_STATE_SEED, config.key().as_ref(), operator.key().as_ref()], bump)]
+    pub voter_state: Account<'info, VoterState>,
+    /// CHECK:
+    #[account()]
+    pub operator: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [restaking_pda::NCN_OPERATOR_STATE_SEED, ncn.key().as_ref(), operator.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub ncn_operator_state: UncheckedAccount<'info>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    /// CHECK:
+    #[account(address = JITO_RESTAKING_ID)]
+    pub jito_restaking_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DelegateOperator<'info> {
+    /// CHECK:
+    #[account(mut, seeds = [vault_pda::CONFIG_SEED], bump, seeds::program = JITO_VAULT_ID)]
+    pub jito_vault_config: UncheckedAccount<'info>,
+    #[account()]
+    pub config: Account<'info, Config>,
+    /// CHECK:
+    #[account(mut, seeds = [vault_pda::VAULT_SEED, config.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [pda::VAULT_ADMIN_SEED, vault.key().as_ref()], bump)]
+    pub vault_admin: SystemAccount<'info>,
+    /// CHECK:
+    #[account()]
+    pub operator: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [vault_pda::VAULT_OPERATOR_DELEGATION_SEED, vault.key().as_ref(), operator.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
+    pub vault_operator_delegation: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK:
+    #[account(address = JITO_VAULT_ID)]
+    pub jito_vault_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct InitializeEpochSnapshot<'info> {
+    #[account()]
+    pub config: Account<'info, Config>,
+    #[account(init, payer = payer,
+        space = EpochSnapshot::DISCRIMINATOR.len() + EpochSnapshot::INIT_SPACE,
+        seeds = [pda::EPOCH_SNAPSHOT_SEED, config.key().as_ref(), &epoch.to_le_bytes()], bump
+    )]
+    pub epoch_snapshot: Account<'info, EpochSnapshot>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeOperatorSnapshot<'info> {
+    #[account()]
+    pub config: Account<'info, Config>,
+    #[account(mut, has_one = config @ MiniNcnError::ConfigMismatch)]
+    pub epoch_snapshot: Account<'info, EpochSnapshot>,
+    #[account(
+        init, payer = payer,
+        space = OperatorSnapshot::DISCRIMINATOR.len() + OperatorSnapshot::INIT_SPACE,
+        seeds = [pda::OPERATOR_SNAPSHOT_SEED, epoch_snapshot.key().as_ref(), operator.key().as_ref()], bump
+    )]
+    pub operator_snapshot: Account<'info, OperatorSnapshot>,
+    #[account(seeds = [pda::VOTER_STATE_SEED, config.key().as_ref(), operator.key().as_ref()], bump)]
+    pub voter_state: Account<'info, VoterState>,
+    /// CHECK:
+    #[account()]
+    pub operator: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SnapshotVaultOperatorDelegation<'info> {
+    #[account()]
+    pub config: Account<'info, Config>,
+    #[account(mut, has_one = config @ MiniNcnError::ConfigMismatch)]
+    pub epoch_snapshot: Account<'info, EpochSnapshot>,
+    #[account(mut, seeds = [pda::OPERATOR_SNAPSHOT_SEED, epoch_snapshot.key().as_ref(), operator.key().as_ref()], bump)]
+    pub operator_snapshot: Account<'info, OperatorSnapshot>,
+    #[account(seeds = [pda::VAULT_ENTRY_SEED, config.key().as_ref(), vault.key().as_ref()], bump)]
+    pub vault_entry: Account<'info, VaultEntry>,
+    #[account(seeds = [pda::WEIGHT_TABLE_SEED, config.key().as_ref(), vault_entry.st_mint.as_ref()], bump)]
+    pub weight_table: Account<'info, WeightTable>,
+    /// CHECK:
+    #[account()]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account()]
+    pub operator: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [vault_pda::VAULT_OPERATOR_DELEGATION_SEED, vault.key().as_ref(), operator.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
+    pub vault_operator_delegation: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [restaking_pda::CONFIG_SEED], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub jito_restaking_config: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [restaking_pda::OPERATOR_VAULT_TICKET_SEED, operator.key().as_ref(), vault.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub operator_vault_ticket: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct Propose<'info> {
+    #[account(mut, seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump, constraint = !config.paused @ MiniNcnError::Paused)]
+    pub config: Account<'info, Config>,
+    #[account(init, payer = payer,
+        space = Proposal::DISCRIMINATOR.len() + Proposal::INIT_SPACE,
+        seeds = [pda::PROPOSAL_SEED, config.key().as_ref(), &config.proposal_count.to_le_bytes()], bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+    #[account(init, payer = payer,
+        space = BallotBox::DISCRIMINATOR.len() + BallotBox::INIT_SPACE,
+        seeds = [pda::BALLOT_BOX_SEED, proposal.key().as_ref()], bump
+    )]
+    pub ballot_box: Account<'info, BallotBox>,
+    #[account(has_one = config @ MiniNcnError::ConfigMismatch)]
+    pub epoch_snapshot: Account<'info, EpochSnapshot>,
+    #[account(constraint = config.is_proposer(&authority.key()) @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeArgs {
+    pub kind: ProposalKind,
+    pub voting_epochs: u64,
+    pub consensus_threshold_bps: Option<u16>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct VoteArgs {
+    pub choice: VoteChoice,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, PartialEq, Eq)]
+pub enum VoteChoice {
+    Approve { ballot: [u8; 32] },
+    Reject,
+    Abstain,
+}
+
+#[derive(Accounts)]
+pub struct Vote<'info> {
+    #[account(constraint = !config.paused @ MiniNcnError::Paused)]
+    pub config: Account<'info, Config>,
+    #[account(has_one = config @ MiniNcnError::ConfigMismatch)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut, seeds = [pda::BALLOT_BOX_SEED, proposal.key().as_ref()], bump)]
+    pub ballot_box: Account<'info, BallotBox>,
+    #[account(init, payer = payer,
+        space = VoteRecord::DISCRIMINATOR.len() + VoteRecord::INIT_SPACE,
+        seeds = [pda::VOTE_RECORD_SEED, proposal.key().as_ref(), operator.key().as_ref()], bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+    #[account(mut, seeds = [pda::VOTER_STATE_SEED, config.key().as_ref(), operator.key().as_ref()], bump)]
+    pub voter_state: Account<'info, VoterState>,
+    pub operator_admin: Signer<'info>,
+    /// CHECK:
+    #[account()]
+    pub operator: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [restaking_pda::CONFIG_SEED], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub jito_restaking_config: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [restaking_pda::NCN_OPERATOR_STATE_SEED, config.ncn.as_ref(), operator.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub ncn_operator_state: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(address = voter_state.operator_vault_ticket @ MiniNcnError::InvalidOperatorVaultTicket)]
+    pub operator_vault_ticket: UncheckedAccount<'info>,
+    #[account(seeds = [pda::EPOCH_SNAPSHOT_SEED, config.key().as_ref(), &proposal.snapshot_epoch.to_le_bytes()], bump)]
+    pub epoch_snapshot: Account<'info, EpochSnapshot>,
+    #[account(seeds = [pda::OPERATOR_SNAPSHOT_SEED, epoch_snapshot.key().as_ref(), operator.key().as_ref()], bump)]
+    pub operator_snapshot: Account<'info, OperatorSnapshot>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+
+#[derive(Accounts)]
+pub struct CheckConsensus<'info> {
+    #[account(mut, seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut, has_one = config @ MiniNcnError::ConfigMismatch)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(mut, seeds = [pda::BALLOT_BOX_SEED, proposal.key().as_ref()], bump)]
+    pub ballot_box: Account<'info, BallotBox>,
+    #[account(seeds = [pda::EPOCH_SNAPSHOT_SEED, config.key().as_ref(), &proposal.snapshot_epoch.to_le_bytes()], bump)]
+    pub epoch_snapshot: Account<'info, EpochSnapshot>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+
+#[derive(Accounts)]
+pub struct RecordMissedVotes<'info> {
+    pub config: Account<'info, Config>,
+    #[account(has_one = config @ MiniNcnError::ConfigMismatch)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(seeds = [pda::BALLOT_BOX_SEED, proposal.key().as_ref()], bump)]
+    pub ballot_box: Account<'info, BallotBox>,
+    /// CHECK: empty if the operator did not vote
+    #[account(seeds = [pda::VOTE_RECORD_SEED, proposal.key().as_ref(), operator.key().as_ref()], bump)]
+    pub vote_record: UncheckedAccount<'info>,
+    #[account(seeds = [pda::EPOCH_SNAPSHOT_SEED, config.key().as_ref(), &proposal.snapshot_epoch.to_le_bytes()], bump)]
+    pub epoch_snapshot: Account<'info, EpochSnapshot>,
+    /// CHECK: empty if the operator is not in the snapshot
+    #[account(seeds = [pda::OPERATOR_SNAPSHOT_SEED, epoch_snapshot.key().as_ref(), operator.key().as_ref()], bump)]
+    pub operator_snapshot: UncheckedAccount<'info>,
+    #[account(mut, seeds = [pda::VOTER_STATE_SEED, config.key().as_ref(), operator.key().as_ref()], bump)]
+    pub voter_state: Account<'info, VoterState>,
+    /// CHECK:
+    #[account()]
+    pub operator: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterSlasher<'info> {
+    #[account(seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK:
+    #[account(seeds = [restaking_pda::CONFIG_SEED], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub jito_restaking_config: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [vault_pda::CONFIG_SEED], bump, seeds::program = JITO_VAULT_ID)]
+    pub jito_vault_config: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, address = config.ncn)]
+    pub ncn: UncheckedAccount<'info>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, ncn.key().as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    #[account(seeds = [pda::VAULT_ENTRY_SEED, config.key().as_ref(), vault.key().as_ref()], bump)]
+    pub vault_entry: Account<'info, VaultEntry>,
+    /// CHECK:
+    #[account(address = vault_entry.vault)]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: the slasher_admin of the vault, checked by the vault program
+    pub vault_slasher_admin: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [restaking_pda::NCN_VAULT_TICKET_SEED, ncn.key().as_ref(), vault.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub ncn_vault_ticket: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [restaking_pda::NCN_VAULT_SLASHER_TICKET_SEED, ncn.key().as_ref(), vault.key().as_ref(), ncn_admin.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub ncn_vault_slasher_ticket: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [vault_pda::VAULT_NCN_SLASHER_TICKET_SEED, vault.key().as_ref(), ncn.key().as_ref(), ncn_admin.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
+    pub vault_ncn_slasher_ticket: UncheckedAccount<'info>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK:
+    #[account(address = JITO_RESTAKING_ID)]
+    pub jito_restaking_program: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(address = JITO_VAULT_ID)]
+    pub jito_vault_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WarmupSlasher<'info> {
+    #[account(seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK:
+    #[account(seeds = [restaking_pda::CONFIG_SEED], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub jito_restaking_config: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [vault_pda::CONFIG_SEED], bump, seeds::program = JITO_VAULT_ID)]
+    pub jito_vault_config: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(address = config.ncn)]
+    pub ncn: UncheckedAccount<'info>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, ncn.key().as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    #[account(seeds = [pda::VAULT_ENTRY_SEED, config.key().as_ref(), vault.key().as_ref()], bump)]
+    pub vault_entry: Account<'info, VaultEntry>,
+    /// CHECK:
+    #[account(address = vault_entry.vault)]
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: the slasher_admin of the vault, checked by the vault program
+    pub vault_slasher_admin: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [restaking_pda::NCN_VAULT_TICKET_SEED, ncn.key().as_ref(), vault.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub ncn_vault_ticket: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [restaking_pda::NCN_VAULT_SLASHER_TICKET_SEED, ncn.key().as_ref(), vault.key().as_ref(), ncn_admin.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
+    pub ncn_vault_slasher_ticket: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [vault_pda::VAULT_NCN_SLASHER_TICKET_SEED, vault.key().as_ref(), ncn.key().as_ref(), ncn_admin.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
+    pub vault_ncn_slasher_ticket: UncheckedAccount<'info>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    /// CHECK:
+    #[account(address = JITO_RESTAKING_ID)]
+    pub jito_restaking_program: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(address = JITO_VAULT_ID)]
+    pub jito_vault_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ForceUndelegate<'info> {
+    #[account(seeds = [pda::CONFIG_SEED, config.ncn.key().as_ref()], bump)]
+    pub config: Account<'info, Config>,
+    // the proposal the operator is penalized for
+    #[account(has_one = config @ MiniNcnError::ConfigMismatch)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(seeds = [pda::BALLOT_BOX_SEED, proposal.key().as_ref()], bump)]
+    pub ballot_box: Account<'info, BallotBox>,
+    /// CHECK: empty if the operator did not vote
+    #[account(seeds = [pda::VOTE_RECORD_SEED, proposal.key().as_ref(), operator.key().as_ref()], bump)]
+    pub vote_record: UncheckedAccount<'info>,
+    #[account(seeds = [pda::EPOCH_SNAPSHOT_SEED, config.key().as_ref(), &proposal.snapshot_epoch.to_le_bytes()], bump)]
+    pub epoch_snapshot: Account<'info, EpochSnapshot>,
+    // only operators in the snapshot were expected to vote
+    #[account(seeds = [pda::OPERATOR_SNAPSHOT_SEED, epoch_snapshot.key().as_ref(), operator.key().as_ref()], bump)]
+    pub operator_snapshot: Account<'info, OperatorSnapshot>,
+    #[account(init, payer = payer,
+        space = UndelegationRecord::DISCRIMINATOR.len() + UndelegationRecord::INIT_SPACE,
+        seeds = [pda::UNDELEGATION_RECORD_SEED, proposal.key().as_ref(), operator.key().as_ref()], bump
+    )]
+    pub undelegation_record: Account<'info, UndelegationRecord>,
+    #[account(init_if_needed, payer = payer,
+        space = UndelegationTally::DISCRIMINATOR.len() + UndelegationTally::INIT_SPACE,
+        seeds = [pda::UNDELEGATION_TALLY_SEED, vault.key().as_ref(), operator.key().as_ref()], bump
+    )]
+    pub undelegation_tally: Account<'info, UndelegationTally>,
+    // an approved undelegation proposal lets anyone execute it
+    pub undelegation_proposal: Option<Account<'info, Proposal>>,
+    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub authority: Option<Signer<'info>>,
+    /// CHECK:
+    #[account(seeds = [vault_pda::CONFIG_SEED], bump, seeds::program = JITO_VAULT_ID)]
+    pub jito_vault_config: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(address = config.ncn)]
+    pub ncn: UncheckedAccount<'info>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, ncn.key().as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    #[account(seeds = [pda::VAULT_ENTRY_SEED, config.key().as_ref(), vault.key().as_ref()], bump)]
+    pub vault_entry: Account<'info, VaultEntry>,
+    /// CHECK:
+    #[account(mut, address = vault_entry.vault)]
+    pub vault: UncheckedAccount<'info>,
+    #[account(seeds = [pda::VAULT_ADMIN_SEED, vault.key().as_ref()], bump)]
+    pub vault_admin: SystemAccount<'info>,
+    /// CHECK:
+    #[account()]
+    pub operator: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(mut, seeds = [vault_pda::VAULT_OPERATOR_DELEGATION_SEED, vault.key().as_ref(), operator.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
+    pub vault_operator_delegation: UncheckedAccount<'info>,
+    /// CHECK:
+    #[account(seeds = [vault_pda::VAULT_NCN_SLASHER_TICKET_SEED, vault.key().as_ref(), ncn.key().as_ref(), ncn_admin.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
+    pub vault_ncn_slasher_ticket: UncheckedAccount<'info>,
+    /// CHECK: address checked against the vault epoch in the handler
+    #[account(mut)]
+    pub vault_ncn_slasher_operator_ticket: UncheckedAccount<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK:
+    #[account(address = JITO_VAULT_ID)]
+    pub jito_vault_program: UncheckedAccount<'info>,
+}
+
+
+#[derive(Accounts)]
+pub struct AddRewardsMint<'info> {
+    pub config: Account<'info, Config>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, config.ncn.as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    #[account(init, payer = payer,
+        space = RewardsVault::DISCRIMINATOR.len() + RewardsVault::INIT_SPACE,
+        seeds = [pda::REWARDS_VAULT_SEED, config.key().as_ref(), rewards_mint.key().as_ref()], bump
+    )]
+    pub rewards_vault: Account<'info, RewardsVault>,
+    #[account(mint::token_program = rewards_token_program)]
+    pub rewards_mint: InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = rewards_mint,
+        associated_token::authority = ncn_admin,
+        associated_token::token_program = rewards_token_program,
+    )]
+    pub rewards_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    #[account(constraint = config.is_rewards_admin(&authority.key()) @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    pub rewards_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+}
+
+
+#[derive(Accounts)]
+pub struct InitializeDistribution<'info> {
+    pub config: Account<'info, Config>,
+    #[account(has_one = config @ MiniNcnError::ConfigMismatch)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(seeds = [pda::BALLOT_BOX_SEED, proposal.key().as_ref()], bump)]
+    pub ballot_box: Account<'info, BallotBox>,
+    #[account(init, payer = payer,
+        space = Distribution::DISCRIMINATOR.len() + Distribution::INIT_SPACE,
+        seeds = [pda::DISTRIBUTION_SEED, config.key().as_ref(), &proposal.snapshot_epoch.to_le_bytes(), rewards_mint.key().as_ref()], bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(
+        seeds = [pda::REWARDS_VAULT_SEED, config.key().as_ref(), rewards_mint.key().as_ref()], bump,
+        has_one = config @ MiniNcnError::ConfigMismatch,
+    )]
+    pub rewards_vault: Account<'info, RewardsVault>,
+    pub rewards_mint: InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
+    #[account(constraint = config.is_rewards_admin(&authority.key()) @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+
+#[derive(Accounts)]
+pub struct InitializeSolDistribution<'info> {
+    pub config: Account<'info, Config>,
+    #[account(has_one = config @ MiniNcnError::ConfigMismatch)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(seeds = [pda::BALLOT_BOX_SEED, proposal.key().as_ref()], bump)]
+    pub ballot_box: Account<'info, BallotBox>,
+    #[account(init, payer = payer,
+        space = Distribution::DISCRIMINATOR.len() + Distribution::INIT_SPACE,
+        seeds = [pda::DISTRIBUTION_SEED, config.key().as_ref(), &proposal.snapshot_epoch.to_le_bytes(), NATIVE_SOL_MINT.as_ref()], bump
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(init_if_needed, payer = payer,
+        space = RewardsSol::DISCRIMINATOR.len() + RewardsSol::INIT_SPACE,
+        seeds = [pda::REWARDS_SOL_SEED, config.key().as_ref()], bump
+    )]
+    pub rewards_sol: Account<'info, RewardsSol>,
+    #[account(constraint = config.is_rewards_admin(&authority.key()) @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+
+#[derive(Accounts)]
+pub struct Clawback<'info> {
+    #[account()]
+    pub config: Account<'info, Config>,
+    #[account(mut,
+        has_one = config @ MiniNcnError::ConfigMismatch,
+        has_one = rewards_mint @ MiniNcnError::InvalidRewardsMint,
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, config.ncn.as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    #[account(mint::token_program = rewards_token_program)]
+    pub rewards_mint: InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
+    #[account(
+        mut,
+        associated_token::mint = rewards_mint,
+        associated_token::authority = ncn_admin,
+        associated_token::token_program = rewards_token_program
+    )]
+    pub rewards_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    #[account(
+        mut,
+        token::mint = rewards_mint.key(),
+        token::authority = config.treasury,
+        token::token_program = rewards_token_program,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    #[account(constraint = config.is_rewards_admin(&authority.key()) @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    pub rewards_token_program: Interface<'info, TokenInterface>,
+}
+
+
+#[derive(Accounts)]
+pub struct ClawbackSol<'info> {
+    #[account()]
+    pub config: Account<'info, Config>,
+    #[account(mut,
+        has_one = config @ MiniNcnError::ConfigMismatch,
+        constraint = distribution.rewards_mint == NATIVE_SOL_MINT @ MiniNcnError::InvalidRewardsMint,
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(mut, seeds = [pda::REWARDS_SOL_SEED, config.key().as_ref()], bump)]
+    pub rewards_sol: Account<'info, RewardsSol>,
+    #[account(mut, address = config.treasury @ MiniNcnError::InvalidTreasury)]
+    pub treasury: SystemAccount<'info>,
+    #[account(constraint = config.is_rewards_admin(&authority.key()) @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+}
+
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    #[account()]
+    pub config: Account<'info, Config>,
+    #[account(mut,
+        has_one = config @ MiniNcnError::ConfigMismatch,
+        has_one = rewards_mint @ MiniNcnError::InvalidRewardsMint,
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, config.ncn.as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    #[account(mint::token_program = rewards_token_program)]
+    pub rewards_mint: InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
+    #[account(
+        mut,
+        associated_token::mint = rewards_mint,
+        associated_token::authority = ncn_admin,
+        associated_token::token_program = rewards_token_program
+    )]
+    pub rewards_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    #[account(
+        mut,
+        token::mint = rewards_mint.key(),
+        token::authority = funder,
+        token::token_program = rewards_token_program,
+    )]
+    pub fund_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    pub funder: Signer<'info>,
+    pub rewards_token_program: Interface<'info, TokenInterface>,
+}
+
+
+#[derive(Accounts)]
+pub struct FundRewardsSol<'info> {
+    #[account()]
+    pub config: Account<'info, Config>,
+    #[account(mut,
+        has_one = config @ MiniNcnError::ConfigMismatch,
+        constraint = distribution.rewards_mint == NATIVE_SOL_MINT @ MiniNcnError::InvalidRewardsMint,
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(mut, seeds = [pda::REWARDS_SOL_SEED, config.key().as_ref()], bump)]
+    pub rewards_sol: Account<'info, RewardsSol>,
+    #[account(mut)]
+    pub funder: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
+
 #[derive(Accounts)]
-pub struct InitializeNcn<'info> {
-    /// CHECK:
-    #[account(mut, seeds = [b"config"], bump, seeds::program = JITO_RESTAKING_ID)]
-    pub jito_restaking_config: UncheckedAccount<'info>,
-    pub base: Signer<'info>,
-    #[account(mut, seeds = [b"ncn", base.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
-    pub ncn: SystemAccount<'info>,
-    #[account(init, payer = payer,
-        space = Config::DISCRIMINATOR.len() + Config::INIT_SPACE,
-        seeds = [b"mini_ncn", ncn.key().as_ref()], bump
-    )]
+pub struct ClaimRewards<'info> {
+    #[account(constraint = !config.paused @ MiniNcnError::Paused)]
     pub config: Account<'info, Config>,
+    #[account(mut,
+        has_one = config @ MiniNcnError::ConfigMismatch,
+        has_one = rewards_mint @ MiniNcnError::InvalidRewardsMint,
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, config.ncn.as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
     #[account(mint::token_program = rewards_token_program)]
     pub rewards_mint: InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
     #[account(
-        init,
-        payer = payer,
+        mut,
         associated_token::mint = rewards_mint,
         associated_token::authority = ncn_admin,
-        associated_token::token_program = rewards_token_program,
+        associated_token::token_program = rewards_token_program
     )]
     pub rewards_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
-    #[account(mut, seeds = [b"ncn_admin", ncn.key().as_ref()], bump)]
-    pub ncn_admin: SystemAccount<'info>,
-    pub authority: Signer<'info>,
+    pub owner: Signer<'info>,
+    #[account(
+        init_if_needed, payer = payer,
+        space = RewardsState::DISCRIMINATOR.len() + RewardsState::INIT_SPACE,
+        seeds = [pda::REWARDS_STATE_SEED, distribution.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub rewards_state: Account<'info, RewardsState>,
+    #[account(mut, token::mint = rewards_mint.key(), token::token_program = rewards_token_program)]
+    pub beneficiary_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
     #[account(mut)]
     pub payer: Signer<'info>,
-    pub system_program: Program<'info, System>,
-    /// CHECK:
-    #[account(address = JITO_RESTAKING_ID)]
-    pub jito_restaking_program: UncheckedAccount<'info>,
     pub rewards_token_program: Interface<'info, TokenInterface>,
-    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
+
 #[derive(Accounts)]
-pub struct InitializeBallotBox<'info> {
-    #[account(seeds = [b"mini_ncn", config.ncn.key().as_ref()], bump)]
+#[instruction(leaf_count: u32)]
+pub struct InitializeClaimBitmap<'info> {
     pub config: Account<'info, Config>,
+    // claim_rewards_with_bitmap only pays out tokens
+    #[account(mut,
+        has_one = config @ MiniNcnError::ConfigMismatch,
+        constraint = distribution.rewards_mint != NATIVE_SOL_MINT @ MiniNcnError::InvalidRewardsMint,
+    )]
+    pub distribution: Account<'info, Distribution>,
     #[account(init, payer = payer,
-        space = BallotBox::DISCRIMINATOR.len() + BallotBox::INIT_SPACE,
-        seeds = [b"ballot_box", config.key().as_ref()], bump
+        space = ClaimBitmap::DISCRIMINATOR.len() + ClaimBitmap::space(leaf_count),
+        seeds = [pda::CLAIM_BITMAP_SEED, distribution.key().as_ref()], bump
     )]
-    pub ballot_box: Account<'info, BallotBox>,
-    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+    #[account(constraint = config.is_rewards_admin(&authority.key()) @ MiniNcnError::InvalidAuthority)]
     pub authority: Signer<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct InitializeBallotBoxArgs {
-    pub max_depth: u32,
-    pub max_buffer_size: u32,
-}
 
 #[derive(Accounts)]
-pub struct InitializeVault<'info> {
-    #[account(seeds = [b"mini_ncn", config.ncn.key().as_ref()], bump)]
+pub struct CloseClaimBitmap<'info> {
     pub config: Account<'info, Config>,
-    /// CHECK:
-    #[account(address = JITO_VAULT_ID)]
-    pub jito_vault_program: UncheckedAccount<'info>,
-    /// CHECK:
-    #[account(mut, seeds = [b"config"], bump, seeds::program = JITO_VAULT_ID)]
-    pub jito_vault_config: UncheckedAccount<'info>,
-    /// CHECK:
-    #[account(mut, seeds = [b"vault", config.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
-    pub vault: SystemAccount<'info>,
-    #[account()]
-    pub st_mint: Account<'info, anchor_spl::token::Mint>,
-    #[account(mut, associated_token::mint = st_mint, associated_token::authority = vault_admin)]
-    pub admin_st_token_account: Account<'info, anchor_spl::token::TokenAccount>,
-    #[account(mut, associated_token::mint = st_mint, associated_token::authority = vault)]
-    pub vault_st_token_account: Account<'info, anchor_spl::token::TokenAccount>,
-    /// CHECK:
-    #[account(mut, seeds = [b"vrt_mint", st_mint.key().as_ref()], bump)]
-    pub vrt_mint: UncheckedAccount<'info>,
-    /// CHECK:
-    #[account(seeds = [b"burn_vault", config.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
-    pub burn_vault: UncheckedAccount<'info>,
-    /// CHECK:
-    #[account(mut, address = anchor_spl::associated_token::get_associated_token_address_with_program_id(burn_vault.key, vrt_mint.key, token_program.key))]
-    pub burn_vault_vrt_token_account: UncheckedAccount<'info>,
-    #[account(mut, seeds = [b"vault_admin", vault.key().as_ref()], bump)]
-    pub vault_admin: SystemAccount<'info>,
-    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
+    #[account(has_one = config @ MiniNcnError::ConfigMismatch)]
+    pub distribution: Account<'info, Distribution>,
+    #[account(mut, close = authority, has_one = distribution @ MiniNcnError::InvalidClaimBitmap)]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+    #[account(mut, constraint = config.is_rewards_admin(&authority.key()) @ MiniNcnError::InvalidAuthority)]
     pub authority: Signer<'info>,
+}
+
+
+#[derive(Accounts)]
+pub struct ClaimRewardsSol<'info> {
+    #[account(constraint = !config.paused @ MiniNcnError::Paused)]
+    pub config: Account<'info, Config>,
+    #[account(mut,
+        has_one = config @ MiniNcnError::ConfigMismatch,
+        constraint = distribution.rewards_mint == NATIVE_SOL_MINT @ MiniNcnError::InvalidRewardsMint,
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(mut, seeds = [pda::REWARDS_SOL_SEED, config.key().as_ref()], bump)]
+    pub rewards_sol: Account<'info, RewardsSol>,
+    pub owner: Signer<'info>,
+    #[account(
+        init_if_needed, payer = payer,
+        space = RewardsState::DISCRIMINATOR.len() + RewardsState::INIT_SPACE,
+        seeds = [pda::REWARDS_STATE_SEED, distribution.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub rewards_state: Account<'info, RewardsState>,
+    #[account(mut)]
+    pub beneficiary: SystemAccount<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, anchor_spl::token::Token>,
-    pub associated_token_program: Program<'info, anchor_spl::associated_token::AssociatedToken>,
 }
 
+
 #[derive(Accounts)]
-pub struct InitializeOperator<'info> {
-    /// CHECK:
-    #[account(mut, seeds = [b"config"], bump, seeds::program = JITO_RESTAKING_ID)]
-    pub jito_restaking_config: UncheckedAccount<'info>,
-    /// CHECK:
-    #[account(mut, seeds = [b"config"], bump, seeds::program = JITO_VAULT_ID)]
-    pub jito_vault_config: UncheckedAccount<'info>,
-    #[account()]
+pub struct InitializeStakeDistribution<'info> {
     pub config: Account<'info, Config>,
-    #[account(seeds = [b"ballot_box", config.key().as_ref()], bump)]
-    pub ballot_box: Account<'info, BallotBox>,
+    #[account(has_one = config @ MiniNcnError::ConfigMismatch)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(seeds = [pda::EPOCH_SNAPSHOT_SEED, config.key().as_ref(), &proposal.snapshot_epoch.to_le_bytes()], bump)]
+    pub epoch_snapshot: Account<'info, EpochSnapshot>,
+    #[account(init, payer = payer,
+        space = StakeDistribution::DISCRIMINATOR.len() + StakeDistribution::INIT_SPACE,
+        seeds = [pda::STAKE_DISTRIBUTION_SEED, proposal.key().as_ref()], bump
+    )]
+    pub stake_distribution: Account<'info, StakeDistribution>,
     #[account(
-        init, payer = payer,
-        space = VoterState::DISCRIMINATOR.len() + VoterState::INIT_SPACE,
-        seeds = [b"voter_state", config.key().as_ref(), operator.key().as_ref()], bump
+        seeds = [pda::REWARDS_VAULT_SEED, config.key().as_ref(), rewards_mint.key().as_ref()], bump,
+        has_one = config @ MiniNcnError::ConfigMismatch,
     )]
-    pub voter_state: Account<'info, VoterState>,
-    /// CHECK:
-    #[account(mut)]
-    pub operator: UncheckedAccount<'info>,
-    pub operator_admin: Signer<'info>,
-    /// CHECK:
-    #[account(mut, seeds = [b"vault", config.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
-    pub vault: UncheckedAccount<'info>,
-    #[account(mut, seeds = [b"vault_admin", vault.key().as_ref()], bump)]
-    pub vault_admin: SystemAccount<'info>,
-    /// CHECK:
-    #[account(mut, seeds = [b"operator_vault_ticket", operator.key().as_ref(), vault.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
-    pub operator_vault_ticket: UncheckedAccount<'info>,
-    /// CHECK:
-    #[account(mut, seeds = [b"vault_operator_delegation", vault.key().as_ref(), operator.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
-    pub vault_operator_delegation: UncheckedAccount<'info>,
+    pub rewards_vault: Account<'info, RewardsVault>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, config.ncn.as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    #[account(mint::token_program = rewards_token_program)]
+    pub rewards_mint: InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
+    #[account(
+        mut,
+        associated_token::mint = rewards_mint,
+        associated_token::authority = ncn_admin,
+        associated_token::token_program = rewards_token_program
+    )]
+    pub rewards_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    #[account(
+        mut,
+        token::mint = rewards_mint.key(),
+        token::authority = funder,
+        token::token_program = rewards_token_program,
+    )]
+    pub fund_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    pub funder: Signer<'info>,
     #[account(mut)]
     pub payer: Signer<'info>,
+    pub rewards_token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
-    /// CHECK:
-    #[account(address = JITO_RESTAKING_ID)]
-    pub jito_restaking_program: UncheckedAccount<'info>,
-    /// CHECK:
-    #[account(address = JITO_VAULT_ID)]
-    pub jito_vault_program: UncheckedAccount<'info>,
 }
 
+
 #[derive(Accounts)]
-pub struct DelegateOperator<'info> {
-    /// CHECK:
-    #[account(mut, seeds = [b"config"], bump, seeds::program = JITO_VAULT_ID)]
-    pub jito_vault_config: UncheckedAccount<'info>,
-    #[account()]
+pub struct SplitStakeRewards<'info> {
+    #[account(constraint = !config.paused @ MiniNcnError::Paused)]
     pub config: Account<'info, Config>,
-    /// CHECK:
-    #[account(mut, seeds = [b"vault", config.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
-    pub vault: UncheckedAccount<'info>,
-    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
-    pub authority: Signer<'info>,
-    #[account(seeds = [b"vault_admin", vault.key().as_ref()], bump)]
-    pub vault_admin: SystemAccount<'info>,
-    /// CHECK:
-    #[account()]
+    #[account(mut,
+        has_one = config @ MiniNcnError::ConfigMismatch,
+        has_one = rewards_mint @ MiniNcnError::InvalidRewardsMint,
+    )]
+    pub stake_distribution: Account<'info, StakeDistribution>,
+    #[account(seeds = [pda::EPOCH_SNAPSHOT_SEED, config.key().as_ref(), &stake_distribution.epoch.to_le_bytes()], bump)]
+    pub epoch_snapshot: Account<'info, EpochSnapshot>,
+    #[account(seeds = [pda::OPERATOR_SNAPSHOT_SEED, epoch_snapshot.key().as_ref(), operator.key().as_ref()], bump)]
+    pub operator_snapshot: Account<'info, OperatorSnapshot>,
+    #[account(seeds = [pda::VOTER_STATE_SEED, config.key().as_ref(), operator.key().as_ref()], bump)]
+    pub voter_state: Account<'info, VoterState>,
+    #[account(init, payer = payer,
+        space = OperatorRewards::DISCRIMINATOR.len() + OperatorRewards::INIT_SPACE,
+        seeds = [pda::OPERATOR_REWARDS_SEED, stake_distribution.key().as_ref(), operator.key().as_ref(), vault.key().as_ref()], bump
+    )]
+    pub operator_rewards: Account<'info, OperatorRewards>,
+    /// CHECK: checked by voter_state
+    #[account(owner = JITO_RESTAKING_ID)]
     pub operator: UncheckedAccount<'info>,
+    #[account(seeds = [pda::VAULT_ENTRY_SEED, config.key().as_ref(), vault.key().as_ref()], bump)]
+    pub vault_entry: Account<'info, VaultEntry>,
+    /// CHECK: checked by vault_entry
+    #[account(mut, owner = JITO_VAULT_ID)]
+    pub vault: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(mut, seeds = [b"vault_operator_delegation", vault.key().as_ref(), operator.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
-    pub vault_operator_delegation: UncheckedAccount<'info>,
+    #[account(seeds = [vault_pda::CONFIG_SEED], bump, seeds::program = JITO_VAULT_ID)]
+    pub jito_vault_config: UncheckedAccount<'info>,
+    /// CHECK: checked by the vault program
+    #[account(mut)]
+    pub vrt_mint: UncheckedAccount<'info>,
+    /// CHECK: checked by the vault program
+    #[account(mut)]
+    pub vault_fee_token_account: UncheckedAccount<'info>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, config.ncn.as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    #[account(mint::token_program = rewards_token_program)]
+    pub rewards_mint: InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
+    #[account(
+        mut,
+        associated_token::mint = rewards_mint,
+        associated_token::authority = ncn_admin,
+        associated_token::token_program = rewards_token_program
+    )]
+    pub rewards_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    #[account(mut, token::mint = rewards_mint.key(), token::token_program = rewards_token_program)]
+    pub operator_fee_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = rewards_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = rewards_token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
     #[account(mut)]
     pub payer: Signer<'info>,
+    pub rewards_token_program: Interface<'info, TokenInterface>,
+    pub vrt_token_program: Program<'info, anchor_spl::token::Token>,
+    pub system_program: Program<'info, System>,
     /// CHECK:
     #[account(address = JITO_VAULT_ID)]
     pub jito_vault_program: UncheckedAccount<'info>,
 }
 
-#[derive(Accounts)]
-pub struct Propose<'info> {
-    #[account(address = ballot_box.config @ MiniNcnError::ConfigMismatch)]
-    pub config: Account<'info, Config>,
-    #[account(mut, seeds = [b"ballot_box", config.key().as_ref()], bump)]
-    pub ballot_box: Account<'info, BallotBox>,
-    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
-    pub authority: Signer<'info>,
-    #[account(mut)]
-    pub payer: Signer<'info>,
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct VoteArgs {
-    pub approved: bool,
-}
 
 #[derive(Accounts)]
-pub struct Vote<'info> {
+pub struct RouteRewards<'info> {
+    #[account(constraint = !config.paused @ MiniNcnError::Paused)]
     pub config: Account<'info, Config>,
-    #[account(mut, seeds = [b"ballot_box", config.key().as_ref()], bump)]
-    pub ballot_box: Account<'info, BallotBox>,
-    #[account(mut, seeds = [b"voter_state", config.key().as_ref(), operator.key().as_ref()], bump)]
+    #[account(seeds = [pda::VOTER_STATE_SEED, config.key().as_ref(), operator.key().as_ref()], bump)]
     pub voter_state: Account<'info, VoterState>,
-    pub operator_admin: Signer<'info>,
-    /// CHECK:
-    #[account(seeds = [b"vault", config.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
-    pub vault: UncheckedAccount<'info>,
-    /// CHECK:
-    #[account()]
+    /// CHECK: checked by voter_state
+    #[account(owner = JITO_RESTAKING_ID)]
     pub operator: UncheckedAccount<'info>,
+    /// CHECK: checked by voter_state
+    #[account(address = voter_state.vault_operator_delegation @ MiniNcnError::InvalidVault)]
+    pub vault_operator_delegation: UncheckedAccount<'info>,
+    /// CHECK: checked against vault_operator_delegation
+    #[account(mut, owner = JITO_VAULT_ID)]
+    pub vault: UncheckedAccount<'info>,
     /// CHECK:
-    #[account(seeds = [b"operator_vault_ticket", operator.key().as_ref(), vault.key().as_ref()], bump, seeds::program = JITO_RESTAKING_ID)]
-    pub operator_vault_ticket: UncheckedAccount<'info>,
+    #[account(seeds = [vault_pda::CONFIG_SEED], bump, seeds::program = JITO_VAULT_ID)]
+    pub jito_vault_config: UncheckedAccount<'info>,
+    /// CHECK: checked by the vault program
+    #[account(mut)]
+    pub vrt_mint: UncheckedAccount<'info>,
+    /// CHECK: checked by the vault program
+    #[account(mut)]
+    pub vault_fee_token_account: UncheckedAccount<'info>,
+    #[account(mint::token_program = rewards_token_program)]
+    pub rewards_mint: InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
+    #[account(
+        mut,
+        token::mint = rewards_mint.key(),
+        token::authority = funder,
+        token::token_program = rewards_token_program,
+    )]
+    pub fund_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    pub funder: Signer<'info>,
+    // owned by the operator admin, checked in the handler
+    #[account(mut, token::mint = rewards_mint.key(), token::token_program = rewards_token_program)]
+    pub operator_fee_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    #[account(
+        mut,
+        associated_token::mint = rewards_mint,
+        associated_token::authority = vault,
+        associated_token::token_program = rewards_token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    #[account(constraint = config.is_rewards_admin(&authority.key()) @ MiniNcnError::InvalidAuthority)]
+    pub authority: Signer<'info>,
+    pub rewards_token_program: Interface<'info, TokenInterface>,
+    pub vrt_token_program: Program<'info, anchor_spl::token::Token>,
     /// CHECK:
-    #[account(seeds = [b"vault_operator_delegation", vault.key().as_ref(), operator.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
-    pub vault_operator_delegation: UncheckedAccount<'info>,
+    #[account(address = JITO_VAULT_ID)]
+    pub jito_vault_program: UncheckedAccount<'info>,
 }
 
 
 #[derive(Accounts)]
-pub struct CheckConsensus<'info> {
-    #[account(address = ballot_box.config @ MiniNcnError::ConfigMismatch)]
+pub struct SetClaimDelegate<'info> {
     pub config: Account<'info, Config>,
-    #[account(mut, seeds = [b"ballot_box", config.key().as_ref()], bump)]
-    pub ballot_box: Account<'info, BallotBox>,
-    /// CHECK:
-    #[account(seeds = [b"vault", config.key().as_ref()], bump, seeds::program = JITO_VAULT_ID)]
-    pub vault: UncheckedAccount<'info>,
-    #[account(address = config.authority @ MiniNcnError::InvalidAuthority)]
-    pub authority: Signer<'info>,
+    #[account(init_if_needed, payer = owner,
+        space = ClaimDelegate::DISCRIMINATOR.len() + ClaimDelegate::INIT_SPACE,
+        seeds = [pda::CLAIM_DELEGATE_SEED, config.key().as_ref(), owner.key().as_ref()], bump
+    )]
+    pub claim_delegate: Account<'info, ClaimDelegate>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
 }
 
 
 #[derive(Accounts)]
-pub struct FundRewards<'info> {
-    #[account()]
+pub struct ClaimRewardsFor<'info> {
+    #[account(constraint = !config.paused @ MiniNcnError::Paused)]
     pub config: Account<'info, Config>,
-    #[account(seeds = [b"ncn_admin", config.ncn.as_ref()], bump)]
+    #[account(mut,
+        has_one = config @ MiniNcnError::ConfigMismatch,
+        has_one = rewards_mint @ MiniNcnError::InvalidRewardsMint,
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, config.ncn.as_ref()], bump)]
     pub ncn_admin: SystemAccount<'info>,
     #[account(mint::token_program = rewards_token_program)]
     pub rewards_mint: InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
@@ -625,25 +2834,68 @@ pub struct FundRewards<'info> {
         associated_token::token_program = rewards_token_program
     )]
     pub rewards_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    /// CHECK: the leaf owner, does not sign
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: empty if the owner has no claim delegate, then rewards go to the owner
+    #[account(seeds = [pda::CLAIM_DELEGATE_SEED, config.key().as_ref(), owner.key().as_ref()], bump)]
+    pub claim_delegate: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed, payer = payer,
+        space = RewardsState::DISCRIMINATOR.len() + RewardsState::INIT_SPACE,
+        seeds = [pda::REWARDS_STATE_SEED, distribution.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub rewards_state: Account<'info, RewardsState>,
+    #[account(mut, token::mint = rewards_mint.key(), token::token_program = rewards_token_program)]
+    pub beneficiary_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub rewards_token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+
+#[derive(Accounts)]
+pub struct ClaimRewardsWithBitmap<'info> {
+    #[account(constraint = !config.paused @ MiniNcnError::Paused)]
+    pub config: Account<'info, Config>,
+    #[account(mut,
+        has_one = config @ MiniNcnError::ConfigMismatch,
+        has_one = rewards_mint @ MiniNcnError::InvalidRewardsMint,
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(mut, has_one = distribution @ MiniNcnError::InvalidClaimBitmap)]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, config.ncn.as_ref()], bump)]
+    pub ncn_admin: SystemAccount<'info>,
+    #[account(mint::token_program = rewards_token_program)]
+    pub rewards_mint: InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
     #[account(
         mut,
-        token::mint = rewards_mint.key(),
-        token::authority = funder,
-        token::token_program = rewards_token_program,
+        associated_token::mint = rewards_mint,
+        associated_token::authority = ncn_admin,
+        associated_token::token_program = rewards_token_program
     )]
-    pub fund_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
-    pub funder: Signer<'info>,
+    pub rewards_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    pub owner: Signer<'info>,
+    #[account(mut, token::mint = rewards_mint.key(), token::token_program = rewards_token_program)]
+    pub beneficiary_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
     pub rewards_token_program: Interface<'info, TokenInterface>,
 }
 
 
 #[derive(Accounts)]
-pub struct ClaimRewards<'info> {
-    #[account()]
+pub struct ClaimRewardsWithBitmapFor<'info> {
+    #[account(constraint = !config.paused @ MiniNcnError::Paused)]
     pub config: Account<'info, Config>,
-    #[account(seeds = [b"ballot_box", config.key().as_ref()], bump)]
-    pub ballot_box: Account<'info, BallotBox>,
-    #[account(seeds = [b"ncn_admin", config.ncn.as_ref()], bump)]
+    #[account(mut,
+        has_one = config @ MiniNcnError::ConfigMismatch,
+        has_one = rewards_mint @ MiniNcnError::InvalidRewardsMint,
+    )]
+    pub distribution: Account<'info, Distribution>,
+    #[account(mut, has_one = distribution @ MiniNcnError::InvalidClaimBitmap)]
+    pub claim_bitmap: Account<'info, ClaimBitmap>,
+    #[account(seeds = [pda::NCN_ADMIN_SEED, config.ncn.as_ref()], bump)]
     pub ncn_admin: SystemAccount<'info>,
     #[account(mint::token_program = rewards_token_program)]
     pub rewards_mint: InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
@@ -654,20 +2906,14 @@ pub struct ClaimRewards<'info> {
         associated_token::token_program = rewards_token_program
     )]
     pub rewards_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
-    pub owner: Signer<'info>,
-    #[account(
-        init_if_needed, payer = payer,
-        space = RewardsState::DISCRIMINATOR.len() + RewardsState::INIT_SPACE,
-        seeds = [b"rewards_state", owner.key().as_ref()],
-        bump
-    )]
-    pub rewards_state: Account<'info, RewardsState>,
+    /// CHECK: the leaf owner, does not sign
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: empty if the owner has no claim delegate, then rewards go to the owner
+    #[account(seeds = [pda::CLAIM_DELEGATE_SEED, config.key().as_ref(), owner.key().as_ref()], bump)]
+    pub claim_delegate: UncheckedAccount<'info>,
     #[account(mut, token::mint = rewards_mint.key(), token::token_program = rewards_token_program)]
     pub beneficiary_token_account: InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
-    #[account(mut)]
-    pub payer: Signer<'info>,
     pub rewards_token_program: Interface<'info, TokenInterface>,
-    pub system_program: Program<'info, System>,
 }
 
 
@@ -683,50 +2929,795 @@ pub struct ClaimRewardsArgs {
 pub struct Config {
     pub ncn: Pubkey,
     pub authority: Pubkey,
+    pub consensus_threshold_bps: u16,
+    pub operator_count: u64,
+    pub vault_count: u64,
+    pub proposal_count: u64,
+    // receives rewards clawed back from expired distributions
+    pub treasury: Pubkey,
+    // set by propose_authority until accepted
+    pub pending_authority: Option<Pubkey>,
+    // optional roles, the authority acts for any role that is unset
+    pub proposer: Option<Pubkey>,
+    pub rewards_admin: Option<Pubkey>,
+    pub pauser: Option<Pubkey>,
+    pub paused: bool,
+}
+
+impl Config {
+    fn has_role(&self, role: Option<Pubkey>, key: &Pubkey) -> bool {
+        *key == self.authority || role == Some(*key)
+    }
+
+    pub fn is_proposer(&self, key: &Pubkey) -> bool {
+        self.has_role(self.proposer, key)
+    }
+
+    pub fn is_rewards_admin(&self, key: &Pubkey) -> bool {
+        self.has_role(self.rewards_admin, key)
+    }
+
+    pub fn is_pauser(&self, key: &Pubkey) -> bool {
+        self.has_role(self.pauser, key)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Proposer,
+    RewardsAdmin,
+    Pauser,
+}
+
+pub fn is_active(
+    slot_toggle: &SlotToggleRef,
+    slot: u64,
+    epoch_length: u64,
+) -> Result<bool> {
+    slot_toggle
+        .is_active(slot, epoch_length)
+        .map_err(|_| error!(MiniNcnError::InvalidEpochLength))
+}
+
+/// Borrows a restaking program account without copying, checking its owner and discriminator
+pub fn restaking_account_ref<'a, T: jito_restaking_client::zero_copy::AccountRef>(
+    account: &'a AccountInfo,
+) -> Result<Ref<'a, T>> {
+    require_keys_eq!(
+        *account.owner,
+        JITO_RESTAKING_ID,
+        MiniNcnError::InvalidRestakingAccountOwner
+    );
+
+    Ref::filter_map(account.try_borrow_data()?, |data| T::try_from_bytes(data).ok())
+        .map_err(|_| error!(MiniNcnError::RestakingAccountMismatch))
+}
+
+/// Borrows a vault program account without copying, checking its owner and discriminator
+pub fn vault_account_ref<'a, T: jito_vault_client::zero_copy::AccountRef>(
+    account: &'a AccountInfo,
+) -> Result<Ref<'a, T>> {
+    require_keys_eq!(
+        *account.owner,
+        JITO_VAULT_ID,
+        MiniNcnError::InvalidVaultAccountOwner
+    );
+
+    Ref::filter_map(account.try_borrow_data()?, |data| T::try_from_bytes(data).ok())
+        .map_err(|_| error!(MiniNcnError::VaultAccountMismatch))
+}
+
+/// Verifies a claim tracked by the RewardsState of its owner and records it,
+/// returning the part of the leaf not paid out yet
+pub fn record_claim(
+    distribution: &mut Account<Distribution>,
+    rewards_state: &mut Account<RewardsState>,
+    owner: Pubkey,
+    args: &ClaimRewardsArgs,
+) -> Result<u64> {
+    require!(
+        !distribution.has_claim_bitmap,
+        MiniNcnError::DistributionUsesClaimBitmap
+    );
+    distribution.verify_claim(&owner, args)?;
+
+    let unclaimed_rewards = args.total_rewards - rewards_state.claimed_rewards;
+    require!(unclaimed_rewards > 0, MiniNcnError::AlreadyClaimed);
+    distribution.record_claimed(unclaimed_rewards)?;
+
+    rewards_state.distribution = distribution.key();
+    rewards_state.owner = owner;
+    rewards_state.claimed_rewards = args.total_rewards;
+
+    Ok(unclaimed_rewards)
+}
+
+/// Verifies a claim tracked by a claim bitmap and marks its leaf, returning the leaf amount
+pub fn record_bitmap_claim(
+    distribution: &mut Account<Distribution>,
+    claim_bitmap: &mut ClaimBitmap,
+    owner: Pubkey,
+    args: &ClaimRewardsArgs,
+) -> Result<u64> {
+    distribution.verify_claim(&owner, args)?;
+
+    // a valid proof is bound to its leaf index, so each leaf is claimed once
+    claim_bitmap.set(args.index)?;
+    distribution.record_claimed(args.total_rewards)?;
+
+    Ok(args.total_rewards)
+}
+
+/// Cranked claims pay the ATA of the claim delegate beneficiary, or of the owner without one
+pub fn check_claim_for_beneficiary(
+    owner: &Pubkey,
+    claim_delegate: &AccountInfo,
+    beneficiary_token_account: &InterfaceAccount<anchor_spl::token_interface::TokenAccount>,
+    token_program: &Pubkey,
+) -> Result<()> {
+    let beneficiary = if claim_delegate.data_is_empty() {
+        *owner
+    } else {
+        ClaimDelegate::try_deserialize(&mut &claim_delegate.try_borrow_data()?[..])?.beneficiary
+    };
+    require_keys_eq!(
+        beneficiary_token_account.key(),
+        anchor_spl::associated_token::get_associated_token_address_with_program_id(
+            &beneficiary,
+            &beneficiary_token_account.mint,
+            token_program,
+        ),
+        MiniNcnError::InvalidBeneficiary
+    );
+
+    Ok(())
+}
+
+/// Pays out rewards tokens held in the ATA of ncn_admin
+pub fn transfer_rewards<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, anchor_spl::token_interface::Mint>,
+    from: &InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    to: &InterfaceAccount<'info, anchor_spl::token_interface::TokenAccount>,
+    ncn_admin: &SystemAccount<'info>,
+    signer_seeds: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    anchor_spl::token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            anchor_spl::token_interface::TransferChecked {
+                mint: mint.to_account_info(),
+                from: from.to_account_info(),
+                to: to.to_account_info(),
+                authority: ncn_admin.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        mint.decimals,
+    )
+}
+
+/// Splits `amount` into (fee, remainder), rounding the fee down
+pub fn split_fee(amount: u64, fee_bps: u16) -> Result<(u64, u64)> {
+    require!(fee_bps <= MAX_BPS, MiniNcnError::InvalidOperatorFee);
+    let fee = (amount as u128 * fee_bps as u128 / MAX_BPS as u128) as u64;
+
+    Ok((fee, amount - fee))
+}
+
+pub fn check_consensus_threshold(consensus_threshold_bps: u16) -> Result<()> {
+    require!(
+        consensus_threshold_bps > 0 && consensus_threshold_bps <= MAX_BPS,
+        MiniNcnError::InvalidConsensusThreshold
+    );
+
+    Ok(())
+}
+
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, PartialEq, Eq)]
+pub enum ProposalKind {
+    // without a proposed root, operators vote the root they computed
+    RewardsRoot(Option<[u8; 32]>),
+    ConfigChange { consensus_threshold_bps: u16 },
+    // force `operator` to undelegate `amount` for how it voted on `proposal`
+    ForceUndelegation {
+        proposal: Pubkey,
+        operator: Pubkey,
+        amount: u64,
+    },
+    // free-form message, e.g. the hash of an off-chain document
+    Message([u8; 32]),
+    // split `amount` across operators by snapshotted stake, without a rewards tree
+    StakeRewards { rewards_mint: Pubkey, amount: u64 },
+}
+
+impl ProposalKind {
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            ProposalKind::ConfigChange {
+                consensus_threshold_bps,
+            } => check_consensus_threshold(*consensus_threshold_bps),
+            ProposalKind::ForceUndelegation { amount, .. }
+            | ProposalKind::StakeRewards { amount, .. } => {
+                require!(*amount > 0, MiniNcnError::InvalidProposal);
+                Ok(())
+            }
+            ProposalKind::RewardsRoot(_) | ProposalKind::Message(_) => Ok(()),
+        }
+    }
+
+    /// The ballot that approves this proposal, `None` if any ballot may win
+    pub fn ballot(&self) -> Option<[u8; 32]> {
+        match self {
+            ProposalKind::RewardsRoot(rewards_root) => *rewards_root,
+            ProposalKind::ConfigChange {
+                consensus_threshold_bps,
+            } => Some(
+                solana_program::keccak::hashv(&[
+                    b"config_change",
+                    &consensus_threshold_bps.to_le_bytes(),
+                ])
+                .to_bytes(),
+            ),
+            ProposalKind::ForceUndelegation {
+                proposal,
+                operator,
+                amount,
+            } => Some(
+                solana_program::keccak::hashv(&[
+                    b"force_undelegation",
+                    proposal.as_ref(),
+                    operator.as_ref(),
+                    &amount.to_le_bytes(),
+                ])
+                .to_bytes(),
+            ),
+            ProposalKind::Message(message) => Some(*message),
+            ProposalKind::StakeRewards {
+                rewards_mint,
+                amount,
+            } => Some(
+                solana_program::keccak::hashv(&[
+                    b"stake_rewards",
+                    rewards_mint.as_ref(),
+                    &amount.to_le_bytes(),
+                ])
+                .to_bytes(),
+            ),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Voting,
+    Approved,
+    Rejected,
 }
 
 
 #[account]
 #[derive(InitSpace)]
-pub struct BallotBox {
+pub struct Proposal {
     pub config: Pubkey,
-    pub epoch: u64,
+    pub id: u64,
+    pub kind: ProposalKind,
+    pub status: ProposalStatus,
+    // voting weights come from the snapshot of this epoch
+    pub snapshot_epoch: u64,
+    // last epoch in which votes are accepted
+    pub end_epoch: u64,
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct BallotBox {
+    pub proposal: Pubkey,
     pub operators_voted: u64,
-    pub approved_votes: u64,
-    pub total_votes: u64,
-    pub rewards_root: [u8; 32],
-    pub proposed_rewards_root: Option<[u8; 32]>,
+    pub approve_stake: u64,
+    pub reject_stake: u64,
+    pub abstain_stake: u64,
+    // approve votes, grouped by ballot
+    #[max_len(MAX_BALLOTS)]
+    pub ballot_tallies: Vec<BallotTally>,
+    pub winning_ballot: Option<[u8; 32]>,
+    pub consensus_threshold_bps: u16,
+    // hashing of rewards roots voted on in this box
+    pub hash_version: HashVersion,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct BallotTally {
+    pub ballot: [u8; 32],
+    pub stake_weight: u64,
+    pub voters: u64,
 }
 
 impl BallotBox {
-    pub fn propose(&mut self, epoch: u64, proposed_rewards_root: [u8; 32]) {
-        self.epoch = epoch;
-        self.operators_voted = 0;
-        self.approved_votes = 0;
-        self.total_votes = 0;
-        self.proposed_rewards_root = Some(proposed_rewards_root);
+    pub fn cast(&mut self, choice: &VoteChoice, stake_weight: u64) -> Result<()> {
+        self.operators_voted += 1;
+
+        let ballot = match choice {
+            VoteChoice::Approve { ballot } => *ballot,
+            VoteChoice::Reject => {
+                self.reject_stake += stake_weight;
+                return Ok(());
+            }
+            VoteChoice::Abstain => {
+                self.abstain_stake += stake_weight;
+                return Ok(());
+            }
+        };
+
+        self.approve_stake += stake_weight;
+        match self.ballot_tallies.iter_mut().find(|t| t.ballot == ballot) {
+            Some(tally) => {
+                tally.stake_weight += stake_weight;
+                tally.voters += 1;
+            }
+            None => {
+                require!(
+                    self.ballot_tallies.len() < MAX_BALLOTS,
+                    MiniNcnError::TooManyBallots
+                );
+                self.ballot_tallies.push(BallotTally {
+                    ballot,
+                    stake_weight,
+                    voters: 1,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn meets_threshold(&self, stake: u64, total_stake: u64) -> bool {
+        stake > 0
+            && stake as u128 * MAX_BPS as u128
+                >= total_stake as u128 * self.consensus_threshold_bps as u128
+    }
+
+    /// The ballot with the most stake, once it reaches `consensus_threshold_bps` of `total_stake`
+    pub fn winning_ballot(&self, total_stake: u64) -> Option<[u8; 32]> {
+        self.ballot_tallies
+            .iter()
+            .max_by_key(|t| t.stake_weight)
+            .filter(|t| self.meets_threshold(t.stake_weight, total_stake))
+            .map(|t| t.ballot)
+    }
+
+    /// Whether any ballot can still win if all remaining stake approves it
+    pub fn approval_possible(&self, total_stake: u64) -> bool {
+        let voted_stake = self.approve_stake + self.reject_stake + self.abstain_stake;
+        let remaining_stake = total_stake.saturating_sub(voted_stake);
+        let leading_stake = self
+            .ballot_tallies
+            .iter()
+            .map(|t| t.stake_weight)
+            .max()
+            .unwrap_or(0);
+
+        self.meets_threshold(leading_stake + remaining_stake, total_stake)
+    }
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub operator: Pubkey,
+    pub choice: VoteChoice,
+    pub stake_weight: u64,
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct EpochSnapshot {
+    pub config: Pubkey,
+    pub epoch: u64,
+    pub operator_count: u64,
+    pub vault_count: u64,
+    pub operators_snapshotted: u64,
+    pub total_stake_weight: u64,
+}
+
+impl EpochSnapshot {
+    pub fn is_finalized(&self) -> bool {
+        self.operators_snapshotted == self.operator_count
+    }
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct OperatorSnapshot {
+    pub epoch_snapshot: Pubkey,
+    pub operator: Pubkey,
+    pub epoch: u64,
+    // bit i is set once the vault with index i is snapshotted
+    pub vault_bitmap: u64,
+    // sum of the operator delegations across vaults, weighted by st_mint
+    pub stake_weight: u64,
+    // the part of stake_weight delegated by the vault with index i
+    pub vault_stake_weights: [u64; MAX_VAULTS as usize],
+}
+
+impl OperatorSnapshot {
+    pub fn is_finalized(&self, vault_count: u64) -> bool {
+        self.vault_bitmap.count_ones() as u64 == vault_count
+    }
+}
+
+
+#[account]
+#[derive(InitSpace)]
+pub struct WeightTable {
+    pub config: Pubkey,
+    pub st_mint: Pubkey,
+    pub weight: u64,
+}
+
+impl WeightTable {
+    pub fn stake_weight(&self, staked_amount: u64) -> Result<u64> {
+        u64::try_from(staked_amount as u128 * self.weight as u128 / WEIGHT_PRECISION as u128)
+            .map_err(|_| error!(MiniNcnError::StakeWeightOverflow))
     }
 }
 
 
+#[account]
+#[derive(InitSpace)]
+pub struct VaultEntry {
+    pub config: Pubkey,
+    pub vault: Pubkey,
+    pub st_mint: Pubkey,
+    pub index: u64,
+}
+
+
 #[account]
 #[derive(InitSpace)]
 pub struct VoterState {
     pub config: Pubkey,
     pub operator: Pubkey,
+    pub index: u64,
     pub operator_vault_ticket: Pubkey,
     pub vault_operator_delegation: Pubkey,
     pub last_voted_epoch: u64,
+    // id of the next proposal for record_missed_votes
+    pub recorded_proposals: u64,
+    pub epochs_participated: u64,
+    pub consecutive_missed_epochs: u64,
+    // end epoch of the last missed proposal
+    pub last_missed_epoch: u64,
+    pub missed_votes: u64,
+    // votes for the winning ballot
+    pub votes_agreeing: u64,
+    // stake weight of the recorded votes, out of eligible_stake_weight
+    pub participated_stake_weight: u64,
+    pub eligible_stake_weight: u64,
+}
+
+// what a slash record would hold: the amount force_undelegate put into cooldown,
+// since the vault program leaves the slasher operator ticket's `slashed` at zero
+#[account]
+#[derive(InitSpace)]
+pub struct UndelegationRecord {
+    pub proposal: Pubkey,
+    pub operator: Pubkey,
+    pub vault: Pubkey,
+    // vault program epoch
+    pub epoch: u64,
+    pub amount: u64,
+    // false if the operator was penalized for not voting
+    pub voted: bool,
+}
+
+// forced undelegations of an operator in a vault during `epoch`, capped by the slasher ticket
+#[account]
+#[derive(InitSpace)]
+pub struct UndelegationTally {
+    pub vault: Pubkey,
+    pub operator: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct RewardsVault {
+    pub config: Pubkey,
+    pub rewards_mint: Pubkey,
+    // SPL Token or Token-2022
+    pub token_program: Pubkey,
+}
+
+// program owned, so claims can move lamports out without a signer
+#[account]
+#[derive(InitSpace)]
+pub struct RewardsSol {
+    pub config: Pubkey,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Distribution {
+    pub config: Pubkey,
+    pub epoch: u64,
+    pub rewards_mint: Pubkey,
+    pub root: [u8; 32],
+    pub hash_version: HashVersion,
+    // sum of the leaves
+    pub total_amount: u64,
+    pub funded_amount: u64,
+    pub claimed_amount: u64,
+    pub clawed_back_amount: u64,
+    // claims close after this epoch
+    pub expiry_epoch: u64,
+    // claims go through the ClaimBitmap instead of RewardsState
+    pub has_claim_bitmap: bool,
+}
+
+impl Distribution {
+    pub fn initialize(
+        &mut self,
+        config: Pubkey,
+        proposal: &Proposal,
+        ballot_box: &BallotBox,
+        rewards_mint: Pubkey,
+        total_amount: u64,
+        expiry_epoch: u64,
+    ) -> Result<()> {
+        require!(
+            matches!(proposal.kind, ProposalKind::RewardsRoot(_))
+                && proposal.status == ProposalStatus::Approved,
+            MiniNcnError::InvalidProposal
+        );
+        require!(
+            expiry_epoch > Clock::get()?.epoch,
+            MiniNcnError::InvalidExpiry
+        );
+
+        self.config = config;
+        self.epoch = proposal.snapshot_epoch;
+        self.rewards_mint = rewards_mint;
+        self.root = ballot_box
+            .winning_ballot
+            .ok_or(MiniNcnError::InvalidProposal)?;
+        self.hash_version = ballot_box.hash_version;
+        self.total_amount = total_amount;
+        self.funded_amount = 0;
+        self.claimed_amount = 0;
+        self.clawed_back_amount = 0;
+        self.expiry_epoch = expiry_epoch;
+        self.has_claim_bitmap = false;
+
+        Ok(())
+    }
+
+    // funding is closed once the distribution expired, which also covers every
+    // distribution clawed back, as clawbacks wait for the expiry
+    pub fn record_funded(&mut self, amount: u64) -> Result<()> {
+        require!(
+            Clock::get()?.epoch <= self.expiry_epoch,
+            MiniNcnError::DistributionFundingClosed
+        );
+        self.funded_amount = self
+            .funded_amount
+            .checked_add(amount)
+            .ok_or(MiniNcnError::RewardsAmountOverflow)?;
+        require!(
+            self.funded_amount <= self.total_amount,
+            MiniNcnError::DistributionOverfunded
+        );
+
+        Ok(())
+    }
+
+    // returns what is left to claw back, and marks it clawed back
+    pub fn record_clawback(&mut self) -> Result<u64> {
+        require!(
+            Clock::get()?.epoch > self.expiry_epoch,
+            MiniNcnError::DistributionNotExpired
+        );
+        let amount = self
+            .funded_amount
+            .checked_sub(self.claimed_amount)
+            .and_then(|amount| amount.checked_sub(self.clawed_back_amount))
+            .ok_or(MiniNcnError::RewardsAmountOverflow)?;
+        self.clawed_back_amount = self
+            .clawed_back_amount
+            .checked_add(amount)
+            .ok_or(MiniNcnError::RewardsAmountOverflow)?;
+
+        Ok(amount)
+    }
+
+    pub fn record_claimed(&mut self, amount: u64) -> Result<()> {
+        self.claimed_amount = self
+            .claimed_amount
+            .checked_add(amount)
+            .ok_or(MiniNcnError::RewardsAmountOverflow)?;
+        require!(
+            self.claimed_amount <= self.funded_amount,
+            MiniNcnError::DistributionUnderfunded
+        );
+
+        Ok(())
+    }
+
+    pub fn verify_claim(&self, owner: &Pubkey, args: &ClaimRewardsArgs) -> Result<()> {
+        require!(
+            Clock::get()?.epoch <= self.expiry_epoch,
+            MiniNcnError::DistributionExpired
+        );
+
+        // recompute only reads the low proof.len() bits of the index, so the
+        // higher bits must be zero for each leaf to have a single index
+        require!(
+            (args.index as u64)
+                .checked_shr(args.proof.len() as u32)
+                .unwrap_or(0)
+                == 0,
+            MiniNcnError::InvalidLeafIndex
+        );
+
+        // the root is verified with the hashing it was voted with
+        let leaf = self
+            .hash_version
+            .hash_leaf(owner, &self.rewards_mint, args.total_rewards);
+        let computed_root = self.hash_version.recompute(leaf, &args.proof, args.index);
+
+        require!(computed_root == self.root, MiniNcnError::InvalidProof);
+
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ClaimDelegate {
+    pub config: Pubkey,
+    pub owner: Pubkey,
+    // wallet whose ATA receives rewards claimed with claim_rewards_for
+    pub beneficiary: Pubkey,
+}
+
+#[account]
+pub struct ClaimBitmap {
+    pub distribution: Pubkey,
+    pub leaf_count: u32,
+    // bit i is set once leaf i is claimed
+    pub bits: Vec<u8>,
+}
+
+impl ClaimBitmap {
+    pub fn bits_len(leaf_count: u32) -> usize {
+        (leaf_count as usize).div_ceil(8)
+    }
+
+    pub fn space(leaf_count: u32) -> usize {
+        32 + 4 + 4 + Self::bits_len(leaf_count)
+    }
+
+    pub fn set(&mut self, index: u32) -> Result<()> {
+        require!(index < self.leaf_count, MiniNcnError::InvalidLeafIndex);
+
+        let (byte, mask) = (index as usize / 8, 1u8 << (index % 8));
+        require!(self.bits[byte] & mask == 0, MiniNcnError::AlreadyClaimed);
+        self.bits[byte] |= mask;
+
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct StakeDistribution {
+    pub config: Pubkey,
+    pub proposal: Pubkey,
+    pub epoch: u64,
+    pub rewards_mint: Pubkey,
+    pub total_amount: u64,
+    // from the epoch snapshot, the shares are stake_weight / total_stake_weight
+    pub total_stake_weight: u64,
+    // (operator, vault) pairs split so far
+    pub operators_split: u64,
+    pub operator_fees: u64,
+    pub vault_rewards: u64,
+}
+
+impl StakeDistribution {
+    /// Share of an operator with `stake_weight`, as (operator fee, vault rewards)
+    pub fn split(&self, stake_weight: u64, operator_fee_bps: u16) -> Result<(u64, u64)> {
+        if self.total_stake_weight == 0 {
+            return Ok((0, 0));
+        }
+
+        let share = (self.total_amount as u128 * stake_weight as u128
+            / self.total_stake_weight as u128) as u64;
+
+        split_fee(share, operator_fee_bps)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct OperatorRewards {
+    pub stake_distribution: Pubkey,
+    pub operator: Pubkey,
+    pub vault: Pubkey,
+    pub stake_weight: u64,
+    pub operator_fee: u64,
+    pub vault_rewards: u64,
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct RewardsState {
+    pub distribution: Pubkey,
     pub owner: Pubkey,
     pub claimed_rewards: u64,
 }
 
 
+#[event]
+pub struct AuthorityProposed {
+    pub config: Pubkey,
+    pub authority: Pubkey,
+    pub pending_authority: Option<Pubkey>,
+}
+
+#[event]
+pub struct AuthorityAccepted {
+    pub config: Pubkey,
+    pub previous_authority: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct RoleChanged {
+    pub config: Pubkey,
+    pub role: Role,
+    pub previous_holder: Option<Pubkey>,
+    pub holder: Option<Pubkey>,
+}
+
+#[event]
+pub struct PausedChanged {
+    pub config: Pubkey,
+    pub paused: bool,
+}
+
+#[event]
+pub struct RewardsClawedBack {
+    pub distribution: Pubkey,
+    pub rewards_mint: Pubkey,
+    pub treasury: Pubkey,
+    pub amount: u64,
+}
+
+
+#[event]
+pub struct RewardsRouted {
+    pub operator: Pubkey,
+    pub vault: Pubkey,
+    pub rewards_mint: Pubkey,
+    pub gross_amount: u64,
+    pub operator_fee: u64,
+    pub vault_rewards: u64,
+    // taken out of vault_rewards by the vault program as VRT
+    pub vault_fee: u64,
+    pub program_fee: u64,
+}
+
+
 #[error_code]
 pub enum MiniNcnError {
     #[msg("Config mismatch")]
@@ -743,12 +3734,104 @@ pub enum MiniNcnError {
     InvalidVaultOperatorDelegation,
     #[msg("Invalid epoch")]
     InvalidEpoch,
-    #[msg("Proposed rewards root already exists")]
-    NonEmptyProposedRoot,
-    #[msg("No proposed rewards root")]
-    EmptyProposedRoot,
     #[msg("Invalid proof")]
     InvalidProof,
     #[msg("Already claimed")]
     AlreadyClaimed,
+    #[msg("Invalid consensus threshold")]
+    InvalidConsensusThreshold,
+    #[msg("Epoch snapshot is not finalized")]
+    EpochSnapshotNotFinalized,
+    #[msg("Operator is not part of the epoch snapshot")]
+    OperatorNotInSnapshot,
+    #[msg("Operator snapshot is not finalized")]
+    OperatorSnapshotNotFinalized,
+    #[msg("Vault is not part of the epoch snapshot")]
+    VaultNotInSnapshot,
+    #[msg("Vault already snapshotted")]
+    VaultAlreadySnapshotted,
+    #[msg("Too many vaults")]
+    TooManyVaults,
+    #[msg("Invalid weight table")]
+    InvalidWeightTable,
+    #[msg("Invalid epoch length")]
+    InvalidEpochLength,
+    #[msg("NCN has not opted in to the operator")]
+    NcnOptInNotActive,
+    #[msg("Operator has not opted in to the NCN")]
+    OperatorOptInNotActive,
+    #[msg("Operator vault ticket is not active")]
+    OperatorVaultTicketNotActive,
+    #[msg("Invalid proposal")]
+    InvalidProposal,
+    #[msg("Invalid voting window")]
+    InvalidVotingWindow,
+    #[msg("Proposal is not open for voting")]
+    ProposalNotVoting,
+    #[msg("Voting window is closed")]
+    VotingWindowClosed,
+    #[msg("Too many distinct ballots")]
+    TooManyBallots,
+    #[msg("Proposal is not finalized")]
+    ProposalNotFinalized,
+    #[msg("Operator voted for the outcome")]
+    OperatorVotedForOutcome,
+    #[msg("Undelegation is not approved")]
+    UndelegationNotApproved,
+    #[msg("Invalid undelegation amount")]
+    InvalidUndelegationAmount,
+    #[msg("Proposals must be recorded in order")]
+    ProposalOutOfOrder,
+    #[msg("Invalid rewards mint")]
+    InvalidRewardsMint,
+    #[msg("Distribution is funded beyond its total amount")]
+    DistributionOverfunded,
+    #[msg("Distribution is not funded enough")]
+    DistributionUnderfunded,
+    #[msg("Invalid expiry")]
+    InvalidExpiry,
+    #[msg("Distribution has not expired")]
+    DistributionNotExpired,
+    #[msg("Distribution has expired")]
+    DistributionExpired,
+    #[msg("Invalid leaf count")]
+    InvalidLeafCount,
+    #[msg("Invalid leaf index")]
+    InvalidLeafIndex,
+    #[msg("Invalid claim bitmap")]
+    InvalidClaimBitmap,
+    #[msg("Distribution already has claims")]
+    DistributionAlreadyClaimed,
+    #[msg("Distribution is claimed through its claim bitmap")]
+    DistributionUsesClaimBitmap,
+    #[msg("Invalid beneficiary")]
+    InvalidBeneficiary,
+    #[msg("Insufficient rewards SOL")]
+    InsufficientRewardsSol,
+    #[msg("Invalid operator fee")]
+    InvalidOperatorFee,
+    #[msg("Paused")]
+    Paused,
+    #[msg("Account is not owned by the Jito restaking program")]
+    InvalidRestakingAccountOwner,
+    #[msg("Unexpected Jito restaking account type")]
+    RestakingAccountMismatch,
+    #[msg("Account is not owned by the Jito vault program")]
+    InvalidVaultAccountOwner,
+    #[msg("Unexpected Jito vault account type")]
+    VaultAccountMismatch,
+    #[msg("Invalid weight")]
+    InvalidWeight,
+    #[msg("Stake weight overflow")]
+    StakeWeightOverflow,
+    #[msg("Undelegations exceed the cap of the epoch")]
+    UndelegationCapExceeded,
+    #[msg("Invalid treasury")]
+    InvalidTreasury,
+    #[msg("Invalid slasher operator ticket")]
+    InvalidSlasherOperatorTicket,
+    #[msg("Rewards amount overflow")]
+    RewardsAmountOverflow,
+    #[msg("Distribution no longer accepts funding")]
+    DistributionFundingClosed,
 }
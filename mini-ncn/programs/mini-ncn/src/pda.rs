@@ -0,0 +1,166 @@
+//! Seeds and address derivation of the mini-ncn accounts, shared by the
+//! account constraints and off-chain code.
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::ID;
+
+pub const CONFIG_SEED: &[u8] = b"mini_ncn";
+pub const NCN_ADMIN_SEED: &[u8] = b"ncn_admin";
+pub const VAULT_ADMIN_SEED: &[u8] = b"vault_admin";
+pub const VRT_MINT_SEED: &[u8] = b"vrt_mint";
+pub const VAULT_ENTRY_SEED: &[u8] = b"vault_entry";
+pub const WEIGHT_TABLE_SEED: &[u8] = b"weight_table";
+pub const VOTER_STATE_SEED: &[u8] = b"voter_state";
+pub const EPOCH_SNAPSHOT_SEED: &[u8] = b"epoch_snapshot";
+pub const OPERATOR_SNAPSHOT_SEED: &[u8] = b"operator_snapshot";
+pub const PROPOSAL_SEED: &[u8] = b"proposal";
+pub const BALLOT_BOX_SEED: &[u8] = b"ballot_box";
+pub const VOTE_RECORD_SEED: &[u8] = b"vote_record";
+pub const UNDELEGATION_RECORD_SEED: &[u8] = b"undelegation_record";
+pub const UNDELEGATION_TALLY_SEED: &[u8] = b"undelegation_tally";
+pub const REWARDS_VAULT_SEED: &[u8] = b"rewards_vault";
+pub const REWARDS_SOL_SEED: &[u8] = b"rewards_sol";
+pub const DISTRIBUTION_SEED: &[u8] = b"distribution";
+pub const CLAIM_BITMAP_SEED: &[u8] = b"claim_bitmap";
+pub const CLAIM_DELEGATE_SEED: &[u8] = b"claim_delegate";
+pub const REWARDS_STATE_SEED: &[u8] = b"rewards_state";
+pub const STAKE_DISTRIBUTION_SEED: &[u8] = b"stake_distribution";
+pub const OPERATOR_REWARDS_SEED: &[u8] = b"operator_rewards";
+
+pub fn find_config_address(ncn: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED, ncn.as_ref()], &ID)
+}
+
+pub fn find_ncn_admin_address(ncn: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NCN_ADMIN_SEED, ncn.as_ref()], &ID)
+}
+
+pub fn find_vault_admin_address(vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_ADMIN_SEED, vault.as_ref()], &ID)
+}
+
+pub fn find_vrt_mint_address(st_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VRT_MINT_SEED, st_mint.as_ref()], &ID)
+}
+
+pub fn find_vault_entry_address(config: &Pubkey, vault: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_ENTRY_SEED, config.as_ref(), vault.as_ref()], &ID)
+}
+
+pub fn find_weight_table_address(config: &Pubkey, st_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WEIGHT_TABLE_SEED, config.as_ref(), st_mint.as_ref()], &ID)
+}
+
+pub fn find_voter_state_address(config: &Pubkey, operator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOTER_STATE_SEED, config.as_ref(), operator.as_ref()], &ID)
+}
+
+pub fn find_epoch_snapshot_address(config: &Pubkey, epoch: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[EPOCH_SNAPSHOT_SEED, config.as_ref(), &epoch.to_le_bytes()],
+        &ID,
+    )
+}
+
+pub fn find_operator_snapshot_address(epoch_snapshot: &Pubkey, operator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            OPERATOR_SNAPSHOT_SEED,
+            epoch_snapshot.as_ref(),
+            operator.as_ref(),
+        ],
+        &ID,
+    )
+}
+
+pub fn find_proposal_address(config: &Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROPOSAL_SEED, config.as_ref(), &id.to_le_bytes()], &ID)
+}
+
+pub fn find_ballot_box_address(proposal: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BALLOT_BOX_SEED, proposal.as_ref()], &ID)
+}
+
+pub fn find_vote_record_address(proposal: &Pubkey, operator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOTE_RECORD_SEED, proposal.as_ref(), operator.as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_undelegation_record_address(proposal: &Pubkey, operator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[UNDELEGATION_RECORD_SEED, proposal.as_ref(), operator.as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_undelegation_tally_address(vault: &Pubkey, operator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[UNDELEGATION_TALLY_SEED, vault.as_ref(), operator.as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_rewards_vault_address(config: &Pubkey, rewards_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REWARDS_VAULT_SEED, config.as_ref(), rewards_mint.as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_rewards_sol_address(config: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REWARDS_SOL_SEED, config.as_ref()], &ID)
+}
+
+pub fn find_distribution_address(
+    config: &Pubkey,
+    epoch: u64,
+    rewards_mint: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            DISTRIBUTION_SEED,
+            config.as_ref(),
+            &epoch.to_le_bytes(),
+            rewards_mint.as_ref(),
+        ],
+        &ID,
+    )
+}
+
+pub fn find_claim_bitmap_address(distribution: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CLAIM_BITMAP_SEED, distribution.as_ref()], &ID)
+}
+
+pub fn find_claim_delegate_address(config: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CLAIM_DELEGATE_SEED, config.as_ref(), owner.as_ref()], &ID)
+}
+
+pub fn find_rewards_state_address(distribution: &Pubkey, owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REWARDS_STATE_SEED, distribution.as_ref(), owner.as_ref()],
+        &ID,
+    )
+}
+
+pub fn find_stake_distribution_address(proposal: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STAKE_DISTRIBUTION_SEED, proposal.as_ref()], &ID)
+}
+
+pub fn find_operator_rewards_address(
+    stake_distribution: &Pubkey,
+    operator: &Pubkey,
+    vault: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            OPERATOR_REWARDS_SEED,
+            stake_distribution.as_ref(),
+            operator.as_ref(),
+            vault.as_ref(),
+        ],
+        &ID,
+    )
+}
@@ -0,0 +1,226 @@
+use std::collections::BTreeSet;
+
+use anchor_lang::{error::ErrorCode, prelude::*, Discriminator, Space};
+use mini_ncn::{
+    pda, BallotBox, CheckConsensus, CheckConsensusBumps, Config, EpochSnapshot, OperatorSnapshot,
+    Proposal, RecordMissedVotes, RecordMissedVotesBumps, SnapshotVaultOperatorDelegation,
+    SnapshotVaultOperatorDelegationBumps, VaultEntry, VoterState, WeightTable,
+};
+
+struct TestAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    data: Vec<u8>,
+}
+
+// an account of the program, decoded from zeros then edited by `f`
+fn program_account<T>(key: Pubkey, f: impl FnOnce(&mut T)) -> TestAccount
+where
+    T: AccountSerialize + AccountDeserialize + Discriminator + Space,
+{
+    let mut data = T::DISCRIMINATOR.to_vec();
+    data.resize(T::DISCRIMINATOR.len() + T::INIT_SPACE, 0);
+    let mut account = T::try_deserialize(&mut &data[..]).unwrap();
+    f(&mut account);
+
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data.resize(T::DISCRIMINATOR.len() + T::INIT_SPACE, 0);
+    TestAccount {
+        key,
+        owner: mini_ncn::ID,
+        data,
+    }
+}
+
+fn empty_account(key: Pubkey) -> TestAccount {
+    TestAccount {
+        key,
+        owner: anchor_lang::system_program::ID,
+        data: Vec::new(),
+    }
+}
+
+// leaked so the infos live as long as the accounts struct decoded from them
+fn account_infos(accounts: Vec<TestAccount>) -> &'static [AccountInfo<'static>] {
+    let infos: Vec<AccountInfo> = accounts
+        .into_iter()
+        .map(|account| {
+            AccountInfo::new(
+                Box::leak(Box::new(account.key)),
+                true,
+                true,
+                Box::leak(Box::new(1_000_000_000)),
+                account.data.leak(),
+                Box::leak(Box::new(account.owner)),
+                false,
+                0,
+            )
+        })
+        .collect();
+    infos.leak()
+}
+
+// decodes `T` from the accounts, then again with each seeded account replaced
+// by a foreign key, which the seeds constraint must reject
+fn check_seeds<T, B>(accounts: impl Fn() -> Vec<TestAccount>, seeded: &[usize])
+where
+    T: Accounts<'static, B>,
+    B: Default,
+{
+    let decode = |accounts: Vec<TestAccount>| {
+        let mut infos = account_infos(accounts);
+        T::try_accounts(
+            &mini_ncn::ID,
+            &mut infos,
+            &[],
+            &mut B::default(),
+            &mut BTreeSet::new(),
+        )
+        .map(|_| ())
+    };
+
+    decode(accounts()).unwrap();
+    for &i in seeded {
+        let mut tampered = accounts();
+        tampered[i].key = Pubkey::new_unique();
+        match decode(tampered) {
+            Err(Error::AnchorError(e)) => {
+                assert_eq!(e.error_code_number, ErrorCode::ConstraintSeeds as u32)
+            }
+            other => panic!("account {i} is not checked by its seeds: {other:?}"),
+        }
+    }
+}
+
+fn config_account(ncn: Pubkey, authority: Pubkey) -> TestAccount {
+    program_account(pda::find_config_address(&ncn).0, |config: &mut Config| {
+        config.ncn = ncn;
+        config.authority = authority;
+    })
+}
+
+fn proposal_account(config: Pubkey, snapshot_epoch: u64) -> TestAccount {
+    program_account(
+        pda::find_proposal_address(&config, 0).0,
+        |proposal: &mut Proposal| {
+            proposal.config = config;
+            proposal.snapshot_epoch = snapshot_epoch;
+        },
+    )
+}
+
+fn epoch_snapshot_account(config: Pubkey, epoch: u64) -> TestAccount {
+    program_account(
+        pda::find_epoch_snapshot_address(&config, epoch).0,
+        |epoch_snapshot: &mut EpochSnapshot| {
+            epoch_snapshot.config = config;
+            epoch_snapshot.epoch = epoch;
+        },
+    )
+}
+
+#[test]
+fn check_consensus_addresses() {
+    let (ncn, authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let config = pda::find_config_address(&ncn).0;
+    let snapshot_epoch = 42;
+    let proposal = proposal_account(config, snapshot_epoch).key;
+
+    check_seeds::<CheckConsensus, CheckConsensusBumps>(
+        || {
+            vec![
+                config_account(ncn, authority),
+                proposal_account(config, snapshot_epoch),
+                program_account(
+                    pda::find_ballot_box_address(&proposal).0,
+                    |_: &mut BallotBox| {},
+                ),
+                epoch_snapshot_account(config, snapshot_epoch),
+                empty_account(authority),
+            ]
+        },
+        &[0, 2, 3],
+    );
+}
+
+#[test]
+fn record_missed_votes_addresses() {
+    let ncn = Pubkey::new_unique();
+    let operator = Pubkey::new_unique();
+    let config = pda::find_config_address(&ncn).0;
+    let snapshot_epoch = 7;
+    let proposal = proposal_account(config, snapshot_epoch).key;
+    let epoch_snapshot = pda::find_epoch_snapshot_address(&config, snapshot_epoch).0;
+
+    check_seeds::<RecordMissedVotes, RecordMissedVotesBumps>(
+        || {
+            vec![
+                config_account(ncn, Pubkey::new_unique()),
+                proposal_account(config, snapshot_epoch),
+                program_account(
+                    pda::find_ballot_box_address(&proposal).0,
+                    |_: &mut BallotBox| {},
+                ),
+                empty_account(pda::find_vote_record_address(&proposal, &operator).0),
+                epoch_snapshot_account(config, snapshot_epoch),
+                empty_account(pda::find_operator_snapshot_address(&epoch_snapshot, &operator).0),
+                program_account(
+                    pda::find_voter_state_address(&config, &operator).0,
+                    |_: &mut VoterState| {},
+                ),
+                empty_account(operator),
+            ]
+        },
+        &[2, 3, 4, 5, 6],
+    );
+}
+
+#[test]
+fn snapshot_vault_operator_delegation_addresses() {
+    let ncn = Pubkey::new_unique();
+    let (vault, operator, st_mint) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let config = pda::find_config_address(&ncn).0;
+    let epoch_snapshot = pda::find_epoch_snapshot_address(&config, 3).0;
+
+    check_seeds::<SnapshotVaultOperatorDelegation, SnapshotVaultOperatorDelegationBumps>(
+        || {
+            vec![
+                config_account(ncn, Pubkey::new_unique()),
+                epoch_snapshot_account(config, 3),
+                program_account(
+                    pda::find_operator_snapshot_address(&epoch_snapshot, &operator).0,
+                    |_: &mut OperatorSnapshot| {},
+                ),
+                program_account(
+                    pda::find_vault_entry_address(&config, &vault).0,
+                    |vault_entry: &mut VaultEntry| vault_entry.st_mint = st_mint,
+                ),
+                program_account(
+                    pda::find_weight_table_address(&config, &st_mint).0,
+                    |_: &mut WeightTable| {},
+                ),
+                empty_account(vault),
+                empty_account(operator),
+                empty_account(
+                    jito_vault_client::pda::find_vault_operator_delegation_address(
+                        &vault, &operator,
+                    )
+                    .0,
+                ),
+                empty_account(jito_restaking_client::pda::find_config_address().0),
+                empty_account(
+                    jito_restaking_client::pda::find_operator_vault_ticket_address(
+                        &operator, &vault,
+                    )
+                    .0,
+                ),
+            ]
+        },
+        &[2, 3, 4, 7, 8, 9],
+    );
+}
@@ -193,11 +193,19 @@ export type TreeNode = {
     right: TreeNode | undefined;
 };
 
+// domain separation since HashVersion::V1, so interior nodes can not pass as leaves
+export const LEAF_PREFIX = Buffer.from([0x00]);
+export const NODE_PREFIX = Buffer.from([0x01]);
+
 /**
  * Uses on-chain hash fn to hash together buffers
  */
 export function hash(left: Buffer, right: Buffer): Buffer {
-    return Buffer.from(keccak_256.digest(Buffer.concat([left, right])));
+    return Buffer.from(keccak_256.digest(Buffer.concat([NODE_PREFIX, left, right])));
+}
+
+export function hashLeaf(...data: Buffer[]): Buffer {
+    return Buffer.from(keccak_256.digest(Buffer.concat([LEAF_PREFIX, ...data])));
 }
 
 /*
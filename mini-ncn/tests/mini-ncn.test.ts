@@ -5,8 +5,11 @@ import { MerkleTree } from "./merkle-tree";
 import { MiniNcn } from "../target/types/mini_ncn";
 import { assert } from "chai";
 import { $ } from "bun";
-import { readFileSync } from "fs";
+import { readFileSync, writeFileSync } from "fs";
 import { buildRewardsTree } from "./rewards-tree";
+import pkg from 'js-sha3';
+
+const { keccak_256 } = pkg;
 
 const JITO_RESTAKING_ID = new web3.PublicKey("RestkWeAVL8fRGgzhfeoqFhsqKRchg6aa1XrcH96z4Q");
 const JITO_VAULT_ID = new web3.PublicKey("Vau1t6sLNxnzB7ZDsef8TLbPLfyZMYXH8WTNqUdm9g8");
@@ -59,6 +62,41 @@ describe("mini-ncn", () => {
 
   const miniNcn = anchor.workspace.MiniNcn as Program<MiniNcn>;
 
+  // highest compute units of each mini-ncn instruction, read back from the ledger once the
+  // suite is done and written to target/compute-units.json. the units are those of the whole
+  // transaction. set CU_BASELINE to an earlier report to compare against it
+  const computeUnits = async () => {
+    const units: Record<string, number> = {};
+
+    let before: string | undefined;
+    for (;;) {
+      const signatures = await provider.connection.getSignaturesForAddress(miniNcn.programId, { before }, "confirmed");
+      if (signatures.length === 0) break;
+      before = signatures[signatures.length - 1].signature;
+
+      for (const { signature, err } of signatures) {
+        if (err) continue;
+
+        const { meta } = await provider.connection.getTransaction(signature, {
+          commitment: "confirmed",
+          maxSupportedTransactionVersion: 0,
+        });
+        // the log right after each top level invoke names the instruction
+        const names = meta.logMessages.flatMap((log, i, logs) => {
+          const name = logs[i - 1] === `Program ${miniNcn.programId} invoke [1]`
+            && log.match(/^Program log: Instruction: (\w+)$/);
+          return name ? [name[1]] : [];
+        });
+        if (names.length === 0) continue;
+
+        const name = names.join("+");
+        units[name] = Math.max(units[name] ?? 0, meta.computeUnitsConsumed);
+      }
+    }
+
+    return units;
+  }
+
   let ncnPubkey: web3.PublicKey;
   let vaultPubkey: web3.PublicKey;
   let vaultAdminPubkey: web3.PublicKey;
@@ -70,9 +108,16 @@ describe("mini-ncn", () => {
   let rewardsMint: web3.Keypair;
   let rewardsTokenAccount: web3.PublicKey;
   let funderTokenAccount: web3.PublicKey;
+  // paid out through a claim bitmap
+  let bitmapMint: web3.Keypair;
 
   let configPubkey: web3.PublicKey;
+  let proposalPubkey: web3.PublicKey;
   let ballotBoxPubkey: web3.PublicKey;
+  let messageProposalPubkey: web3.PublicKey;
+  let messageBallotBoxPubkey: web3.PublicKey;
+  let noticeProposalPubkey: web3.PublicKey;
+  let stakeRewardsProposalPubkey: web3.PublicKey;
 
   before(async () => {
     // prepare NCN
@@ -107,6 +152,31 @@ describe("mini-ncn", () => {
       null,
       rewardsTokenProgram,
     )
+
+    bitmapMint = web3.Keypair.generate();
+    await spl.createMint(
+      provider.connection,
+      provider.wallet.payer,
+      authority.publicKey,
+      null,
+      9,
+      bitmapMint,
+      null,
+      rewardsTokenProgram,
+    )
+  });
+
+  after(async () => {
+    const units = await computeUnits();
+    const baseline = process.env.CU_BASELINE
+      ? JSON.parse(readFileSync(process.env.CU_BASELINE, 'utf8'))
+      : {};
+    console.table(Object.fromEntries(Object.entries(units).map(([name, after]) => [name, {
+      before: baseline[name],
+      after,
+      delta: baseline[name] === undefined ? undefined : after - baseline[name],
+    }])));
+    writeFileSync('target/compute-units.json', JSON.stringify(units, null, 2));
   });
 
   it("initialize ncn", async () => {
@@ -116,8 +186,6 @@ describe("mini-ncn", () => {
       .accountsPartial({
         base: base.publicKey,
         authority: authority.publicKey,
-        rewardsMint: rewardsMint.publicKey,
-        rewardsTokenProgram,
       })
       .signers([authority, base])
 
@@ -126,44 +194,142 @@ describe("mini-ncn", () => {
 
     ncnPubkey = pubkeys.ncn;
 
-    rewardsTokenAccount = spl.getAssociatedTokenAddressSync(
-      rewardsMint.publicKey,
-      pubkeys.ncnAdmin,
-      true,
-      rewardsTokenProgram,
-    )
-
     await tx.rpc();
 
     configPubkey = pubkeys.config;
 
     const config = await miniNcn.account.config.fetch(configPubkey);
     assert.ok(config.authority.equals(authority.publicKey));
+    assert.equal(config.consensusThresholdBps, 6667);
 
     const ncnAdmin = await provider.connection.getAccountInfo(pubkeys.ncnAdmin);
     console.log('ncn admin', ncnAdmin);
   })
 
 
-  it("initialize ballot box", async () => {
+  it("add rewards mint", async () => {
     const tx = miniNcn.methods
-      .initializeBallotBox()
-      .accountsPartial({
+      .addRewardsMint()
+      .accounts({
         config: configPubkey,
+        rewardsMint: rewardsMint.publicKey,
         authority: authority.publicKey,
+        rewardsTokenProgram,
       })
       .signers([authority])
 
     const pubkeys = await tx.pubkeys();
     debugPubkeys(pubkeys);
+    await tx.rpc();
 
-    const signature = await tx.rpc();
+    rewardsTokenAccount = pubkeys.rewardsTokenAccount;
 
-    ballotBoxPubkey = pubkeys.ballotBox;
+    const rewardsVault = await miniNcn.account.rewardsVault.fetch(pubkeys.rewardsVault);
+    assert.ok(rewardsVault.rewardsMint.equals(rewardsMint.publicKey));
+    assert.ok(rewardsVault.tokenProgram.equals(rewardsTokenProgram));
+  })
 
-    const ballotBox = await miniNcn.account.ballotBox.fetch(ballotBoxPubkey);
-    assert.ok(ballotBox.config.equals(configPubkey));
 
+  it("set consensus threshold", async () => {
+    await miniNcn.methods
+      .setConsensusThreshold(5001)
+      .accountsPartial({
+        config: configPubkey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    const config = await miniNcn.account.config.fetch(configPubkey);
+    assert.equal(config.consensusThresholdBps, 5001);
+  });
+
+
+  it("hand authority over and back", async () => {
+    const newAuthority = web3.Keypair.generate();
+    const handOver = async (from: web3.Keypair, to: web3.Keypair) => {
+      await miniNcn.methods
+        .proposeAuthority(to.publicKey)
+        .accountsPartial({
+          config: configPubkey,
+          authority: from.publicKey,
+        })
+        .signers([from])
+        .rpc();
+
+      await miniNcn.methods
+        .acceptAuthority()
+        .accountsPartial({
+          config: configPubkey,
+          pendingAuthority: to.publicKey,
+        })
+        .signers([to])
+        .rpc();
+    }
+
+    await handOver(authority, newAuthority);
+    let config = await miniNcn.account.config.fetch(configPubkey);
+    assert.ok(config.authority.equals(newAuthority.publicKey));
+    assert.isNull(config.pendingAuthority);
+
+    await handOver(newAuthority, authority);
+    config = await miniNcn.account.config.fetch(configPubkey);
+    assert.ok(config.authority.equals(authority.publicKey));
+
+    // a pending handover can be cancelled
+    const proposeAuthority = (to: web3.PublicKey | null) => miniNcn.methods
+      .proposeAuthority(to)
+      .accountsPartial({
+        config: configPubkey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    await proposeAuthority(newAuthority.publicKey);
+    await proposeAuthority(null);
+    config = await miniNcn.account.config.fetch(configPubkey);
+    assert.isNull(config.pendingAuthority);
+
+    try {
+      await miniNcn.methods
+        .acceptAuthority()
+        .accountsPartial({
+          config: configPubkey,
+          pendingAuthority: newAuthority.publicKey,
+        })
+        .signers([newAuthority])
+        .rpc();
+      assert.fail("handover was cancelled");
+    } catch (e) {
+      assert.include(e.toString(), "InvalidAuthority");
+    }
+  });
+
+
+  it("pauser pauses and unpauses", async () => {
+    await miniNcn.methods
+      .setRole({ pauser: {} }, userKeypair.publicKey)
+      .accountsPartial({
+        config: configPubkey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    for (const paused of [true, false]) {
+      await miniNcn.methods
+        .setPaused(paused)
+        .accountsPartial({
+          config: configPubkey,
+          authority: userKeypair.publicKey,
+        })
+        .signers([userKeypair])
+        .rpc();
+
+      const config = await miniNcn.account.config.fetch(configPubkey);
+      assert.equal(config.paused, paused);
+    }
   });
 
 
@@ -258,6 +424,68 @@ describe("mini-ncn", () => {
   })
 
 
+  let weightTablePubkey: web3.PublicKey;
+  it("set weight", async () => {
+    try {
+      await miniNcn.methods
+        .setWeight(new BN(1_000_000_000).muln(1_001))
+        .accountsPartial({
+          config: configPubkey,
+          stMint: stMint.publicKey,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+      assert.fail("weight above the cap should be rejected");
+    } catch (e) {
+      assert.include(e.toString(), "InvalidWeight");
+    }
+
+    const tx = miniNcn.methods
+      .setWeight(new BN(1_000_000_000))
+      .accountsPartial({
+        config: configPubkey,
+        stMint: stMint.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+
+    const pubkeys = await tx.pubkeys();
+    debugPubkeys(pubkeys);
+
+    await tx.rpc();
+
+    weightTablePubkey = pubkeys.weightTable;
+
+    const weightTable = await miniNcn.account.weightTable.fetch(weightTablePubkey);
+    assert.equal(weightTable.weight.toNumber(), 1_000_000_000);
+  })
+
+
+  it("register vault", async () => {
+    const tx = miniNcn.methods
+      .registerVault()
+      .accountsPartial({
+        config: configPubkey,
+        ncn: ncnPubkey,
+        vault: vaultPubkey,
+        vaultNcnAdmin: vaultAdminPubkey,
+        weightTable: weightTablePubkey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+
+    const pubkeys = await tx.pubkeys();
+    debugPubkeys(pubkeys);
+
+    await tx.rpc();
+
+    const vaultEntry = await miniNcn.account.vaultEntry.fetch(pubkeys.vaultEntry);
+    assert.ok(vaultEntry.stMint.equals(stMint.publicKey));
+    assert.equal(vaultEntry.index.toNumber(), 0);
+  })
+
+
   let op0Pubkey: web3.PublicKey;
   let op1Pubkey: web3.PublicKey;
   it("initialize operators", async () => {
@@ -268,10 +496,33 @@ describe("mini-ncn", () => {
     const op1Output = await $`${jitoCliOp1} restaking operator initialize 2000`
     op1Pubkey = getInitializedAddress(op1Output.stderr.toString());
 
+    // only Jito restaking operators can join
+    for (const [operator, error] of [
+      [userKeypair.publicKey, "InvalidRestakingAccountOwner"],
+      [ncnPubkey, "RestakingAccountMismatch"],
+    ] as const) {
+      try {
+        await miniNcn.methods
+          .initializeOperator()
+          .accountsPartial({
+            config: configPubkey,
+            ncn: ncnPubkey,
+            operatorAdmin: op0AdminKeypair.publicKey,
+            operator,
+          })
+          .signers([op0AdminKeypair])
+          .rpc();
+        assert.fail("not an operator");
+      } catch (e) {
+        assert.include(e.toString(), error);
+      }
+    }
+
     const tx = miniNcn.methods
       .initializeOperator()
-      .accounts({
+      .accountsPartial({
         config: configPubkey,
+        ncn: ncnPubkey,
         operatorAdmin: op0AdminKeypair.publicKey,
         operator: op0Pubkey,
       })
@@ -282,8 +533,9 @@ describe("mini-ncn", () => {
 
     await miniNcn.methods
       .initializeOperator()
-      .accounts({
+      .accountsPartial({
         config: configPubkey,
+        ncn: ncnPubkey,
         operatorAdmin: op1AdminKeypair.publicKey,
         operator: op1Pubkey,
       })
@@ -338,47 +590,205 @@ describe("mini-ncn", () => {
   })
 
 
+  it("warmup vault and operators", async () => {
+    // tickets can only be warmed up in the next epoch
+    await nextEpoch();
+
+    await miniNcn.methods
+      .warmupVault()
+      .accountsPartial({
+        config: configPubkey,
+        ncn: ncnPubkey,
+        vault: vaultPubkey,
+        vaultNcnAdmin: vaultAdminPubkey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    for (const operator of [op0Pubkey, op1Pubkey]) {
+      await miniNcn.methods
+        .warmupOperator()
+        .accountsPartial({
+          config: configPubkey,
+          ncn: ncnPubkey,
+          operator,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+    }
+
+    // operators opt in on their side
+    await $`${jitoCliOp0} restaking operator operator-warmup-ncn ${op0Pubkey} ${ncnPubkey}`
+    await $`${jitoCliOp0} restaking operator warmup-operator-vault-ticket ${op0Pubkey} ${vaultPubkey}`
+    await $`${jitoCliOp1} restaking operator operator-warmup-ncn ${op1Pubkey} ${ncnPubkey}`
+    await $`${jitoCliOp1} restaking operator warmup-operator-vault-ticket ${op1Pubkey} ${vaultPubkey}`
+
+    // opt-ins become active in the next epoch
+    await nextEpoch();
+  })
+
+
+  let epochSnapshotPubkey: web3.PublicKey;
+
+  it("snapshot operators", async () => {
+
+    const { epoch } = await provider.connection.getEpochInfo();
+    const tx = miniNcn.methods
+      .initializeEpochSnapshot(new BN(epoch))
+      .accounts({
+        config: configPubkey,
+      })
+
+    const pubkeys = await tx.pubkeys();
+    debugPubkeys(pubkeys);
+
+    await tx.rpc();
+
+    epochSnapshotPubkey = pubkeys.epochSnapshot;
+
+    // anyone can crank the snapshot
+    for (const operator of [op0Pubkey, op1Pubkey]) {
+      await miniNcn.methods
+        .initializeOperatorSnapshot()
+        .accounts({
+          config: configPubkey,
+          epochSnapshot: epochSnapshotPubkey,
+          operator,
+        })
+        .rpc();
+
+      await miniNcn.methods
+        .snapshotVaultOperatorDelegation()
+        .accounts({
+          config: configPubkey,
+          epochSnapshot: epochSnapshotPubkey,
+          vault: vaultPubkey,
+          operator,
+        })
+        .rpc();
+    }
+
+    const epochSnapshot = await miniNcn.account.epochSnapshot.fetch(epochSnapshotPubkey);
+    assert.equal(epochSnapshot.operatorsSnapshotted.toNumber(), 2);
+    assert.equal(epochSnapshot.totalStakeWeight.toNumber(), 1234567890);
+  });
+
+
   let userRewards;
   let rewardsTree: MerkleTree;
 
   it("propose", async () => {
-    await nextEpoch();
-
     userRewards = [{
       user: op0AdminKeypair.publicKey,
+      mint: rewardsMint.publicKey,
       amount: 123456789n,
     }, {
       user: op1AdminKeypair.publicKey,
+      mint: rewardsMint.publicKey,
       amount: 987654321n,
+    }, {
+      // the system program id marks lamport payouts
+      user: op0AdminKeypair.publicKey,
+      mint: web3.SystemProgram.programId,
+      amount: BigInt(web3.LAMPORTS_PER_SOL / 2),
+    }, {
+      user: op1AdminKeypair.publicKey,
+      mint: bitmapMint.publicKey,
+      amount: 555555555n,
     }];
 
     rewardsTree = buildRewardsTree(userRewards);
 
-    const data = Array.from(rewardsTree.root);
+    try {
+      await miniNcn.methods
+        .propose({
+          kind: { rewardsRoot: [null] },
+          votingEpochs: new BN("18446744073709551615"),
+          consensusThresholdBps: null,
+        })
+        .accounts({
+          config: configPubkey,
+          epochSnapshot: epochSnapshotPubkey,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+      assert.fail("an unbounded voting window should be rejected");
+    } catch (e) {
+      assert.include(e.toString(), "InvalidVotingWindow");
+    }
+
+    // operators submit the root they computed themselves
     const tx = miniNcn.methods
-      .propose(data)
+      .propose({
+        kind: { rewardsRoot: [null] },
+        votingEpochs: new BN(1),
+        consensusThresholdBps: 6667,
+      })
       .accounts({
         config: configPubkey,
+        epochSnapshot: epochSnapshotPubkey,
         authority: authority.publicKey,
       })
       .signers([authority])
 
-    debugPubkeys(await tx.pubkeys());
+    const pubkeys = await tx.pubkeys();
+    debugPubkeys(pubkeys);
 
     await tx.rpc();
 
+    proposalPubkey = pubkeys.proposal;
+    ballotBoxPubkey = pubkeys.ballotBox;
+
+    const proposal = await miniNcn.account.proposal.fetch(proposalPubkey);
+    assert.isNull(proposal.kind.rewardsRoot[0]);
+    assert.deepEqual(proposal.status, { voting: {} });
+
     const ballotBox = await miniNcn.account.ballotBox.fetch(ballotBoxPubkey);
-    assert.deepEqual(ballotBox.proposedRewardsRoot, data);
+    assert.equal(ballotBox.consensusThresholdBps, 6667);
+  });
+
+
+  it("propose message alongside", async () => {
+    // several proposals can be live at once
+    const message = Array.from(Buffer.alloc(32, 1));
+    const tx = miniNcn.methods
+      .propose({
+        kind: { message: [message] },
+        votingEpochs: new BN(2),
+        consensusThresholdBps: null,
+      })
+      .accounts({
+        config: configPubkey,
+        epochSnapshot: epochSnapshotPubkey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+
+    const pubkeys = await tx.pubkeys();
+    await tx.rpc();
+    messageProposalPubkey = pubkeys.proposal;
+    messageBallotBoxPubkey = pubkeys.ballotBox;
+
+    const proposal = await miniNcn.account.proposal.fetch(pubkeys.proposal);
+    assert.equal(proposal.id.toNumber(), 1);
+    assert.equal(proposal.endEpoch.toNumber(), proposal.snapshotEpoch.toNumber() + 1);
+
+    const ballotBox = await miniNcn.account.ballotBox.fetch(pubkeys.ballotBox);
+    assert.equal(ballotBox.consensusThresholdBps, 5001);
   });
 
 
   it("op0 vote", async () => {
     const tx = miniNcn.methods
       .vote({
-        approved: true
+        choice: { approve: { ballot: Array.from(rewardsTree.root) } },
       })
-      .accounts({
+      .accountsPartial({
         config: configPubkey,
+        proposal: proposalPubkey,
         operatorAdmin: op0AdminKeypair.publicKey,
         operator: op0Pubkey,
       })
@@ -390,7 +800,8 @@ describe("mini-ncn", () => {
 
     const ballotBox = await miniNcn.account.ballotBox.fetch(ballotBoxPubkey);
     assert.equal(ballotBox.operatorsVoted.toNumber(), 1);
-    assert.equal(ballotBox.approvedVotes.toNumber(), 234567890);
+    assert.equal(ballotBox.approveStake.toNumber(), 234567890);
+    assert.equal(ballotBox.ballotTallies[0].stakeWeight.toNumber(), 234567890);
   });
 
 
@@ -399,6 +810,7 @@ describe("mini-ncn", () => {
       .checkConsensus()
       .accounts({
         config: configPubkey,
+        proposal: proposalPubkey,
         authority: authority.publicKey,
       })
       .signers([authority])
@@ -407,18 +819,19 @@ describe("mini-ncn", () => {
 
     await tx.rpc();
 
-    const ballotBox = await miniNcn.account.ballotBox.fetch(ballotBoxPubkey);
-    assert.isNotNull(ballotBox.proposedRewardsRoot);
+    const proposal = await miniNcn.account.proposal.fetch(proposalPubkey);
+    assert.deepEqual(proposal.status, { voting: {} });
   })
 
 
   it("op1 vote", async () => {
     const tx = miniNcn.methods
       .vote({
-        approved: true
+        choice: { approve: { ballot: Array.from(rewardsTree.root) } },
       })
-      .accounts({
+      .accountsPartial({
         config: configPubkey,
+        proposal: proposalPubkey,
         operatorAdmin: op1AdminKeypair.publicKey,
         operator: op1Pubkey,
       })
@@ -430,7 +843,10 @@ describe("mini-ncn", () => {
 
     const ballotBox = await miniNcn.account.ballotBox.fetch(ballotBoxPubkey);
     assert.equal(ballotBox.operatorsVoted.toNumber(), 2);
-    assert.equal(ballotBox.approvedVotes.toNumber(), 1234567890);
+    assert.equal(ballotBox.approveStake.toNumber(), 1234567890);
+    assert.equal(ballotBox.ballotTallies.length, 1);
+    assert.equal(ballotBox.ballotTallies[0].stakeWeight.toNumber(), 1234567890);
+    assert.equal(ballotBox.ballotTallies[0].voters.toNumber(), 2);
   });
 
 
@@ -439,84 +855,623 @@ describe("mini-ncn", () => {
       .checkConsensus()
       .accounts({
         config: configPubkey,
+        proposal: proposalPubkey,
         authority: authority.publicKey,
       })
       .signers([authority])
       .rpc();
 
+    const proposal = await miniNcn.account.proposal.fetch(proposalPubkey);
+    assert.deepEqual(proposal.status, { approved: {} });
+
     const ballotBox = await miniNcn.account.ballotBox.fetch(ballotBoxPubkey);
-    assert.isNull(ballotBox.proposedRewardsRoot);
+    assert.deepEqual(ballotBox.winningBallot, Array.from(rewardsTree.root));
+    assert.deepEqual(ballotBox.hashVersion, { v2: {} });
   })
 
-  const fundAmount = 1000_000_000_000n;
-  it("fund rewards", async () => {
-    await spl.mintToChecked(
-      provider.connection,
-      provider.wallet.payer,
-      rewardsMint.publicKey,
-      funderTokenAccount,
-      authority,
-      1000_000_000_000,
-      9,
-      [],
-      null,
-      rewardsTokenProgram,
-    )
+  it("op1 reject message", async () => {
+    await miniNcn.methods
+      .vote({
+        choice: { reject: {} },
+      })
+      .accountsPartial({
+        config: configPubkey,
+        proposal: messageProposalPubkey,
+        operatorAdmin: op1AdminKeypair.publicKey,
+        operator: op1Pubkey,
+      })
+      .signers([op1AdminKeypair])
+      .rpc();
+
+    // op1 holds most of the stake, so the message can no longer pass
+    await miniNcn.methods
+      .checkConsensus()
+      .accounts({
+        config: configPubkey,
+        proposal: messageProposalPubkey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
 
+    const proposal = await miniNcn.account.proposal.fetch(messageProposalPubkey);
+    assert.deepEqual(proposal.status, { rejected: {} });
+
+    const ballotBox = await miniNcn.account.ballotBox.fetch(messageBallotBoxPubkey);
+    assert.equal(ballotBox.rejectStake.toNumber(), 1000000000);
+    assert.equal(ballotBox.ballotTallies.length, 0);
+  })
+
+  it("op1 approve notice alone", async () => {
+    const notice = Array.from(Buffer.alloc(32, 2));
     const tx = miniNcn.methods
-      .fundRewards(new BN(fundAmount.toString()))
+      .propose({
+        kind: { message: [notice] },
+        votingEpochs: new BN(2),
+        consensusThresholdBps: null,
+      })
       .accounts({
         config: configPubkey,
-        rewardsMint: rewardsMint.publicKey,
-        funder: authority.publicKey,
-        fundTokenAccount: funderTokenAccount,
-        rewardsTokenProgram,
+        epochSnapshot: epochSnapshotPubkey,
+        authority: authority.publicKey,
       })
       .signers([authority])
 
-    debugPubkeys(await tx.pubkeys());
+    const pubkeys = await tx.pubkeys();
     await tx.rpc();
+    noticeProposalPubkey = pubkeys.proposal;
 
-    const rewardsTokenAccountInfo = await spl.getAccount(provider.connection, rewardsTokenAccount);
-    assert.equal(rewardsTokenAccountInfo.amount, fundAmount);
-  })
+    await miniNcn.methods
+      .vote({
+        choice: { approve: { ballot: notice } },
+      })
+      .accountsPartial({
+        config: configPubkey,
+        proposal: noticeProposalPubkey,
+        operatorAdmin: op1AdminKeypair.publicKey,
+        operator: op1Pubkey,
+      })
+      .signers([op1AdminKeypair])
+      .rpc();
 
-  it("claim rewards for op0", async () => {
-    const { proof, leafIndex, root } = rewardsTree.getProof(0)
+    await miniNcn.methods
+      .checkConsensus()
+      .accounts({
+        config: configPubkey,
+        proposal: noticeProposalPubkey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
 
-    const ballotBox = await miniNcn.account.ballotBox.fetch(ballotBoxPubkey);
-    assert.deepEqual(ballotBox.rewardsRoot, Array.from(root));
+    const proposal = await miniNcn.account.proposal.fetch(noticeProposalPubkey);
+    assert.deepEqual(proposal.status, { approved: {} });
+  })
 
-    const beneficiaryTokenAccount = await spl.createAssociatedTokenAccount(
-      provider.connection,
-      provider.wallet.payer,
-      rewardsMint.publicKey,
-      op0AdminKeypair.publicKey,
-      null,
-      rewardsTokenProgram,
-    )
+  it("split stake rewards", async () => {
+    const amount = 1_000_000_000n;
+    // the vault share is paid in the token the vault holds
+    const stakeRewardsMint = stMint.publicKey;
+    const amountBuffer = Buffer.alloc(8);
+    amountBuffer.writeBigUInt64LE(amount, 0);
 
     const tx = miniNcn.methods
-      .claimRewards({
-        index: leafIndex,
-        totalRewards: new BN(userRewards[leafIndex].amount.toString()),
-        proof: proof.map(node => Array.from(node)),
+      .propose({
+        kind: { stakeRewards: { rewardsMint: stakeRewardsMint, amount: new BN(amount.toString()) } },
+        votingEpochs: new BN(2),
+        consensusThresholdBps: null,
       })
       .accounts({
         config: configPubkey,
-        rewardsMint: rewardsMint.publicKey,
-        owner: op0AdminKeypair.publicKey,
-        beneficiaryTokenAccount,
-        rewardsTokenProgram,
+        epochSnapshot: epochSnapshotPubkey,
+        authority: authority.publicKey,
       })
-      .signers([op0AdminKeypair])
-
-    const pubkeys = await tx.pubkeys();
-    debugPubkeys(pubkeys);
+      .signers([authority])
 
+    const { proposal } = await tx.pubkeys();
     await tx.rpc();
+    stakeRewardsProposalPubkey = proposal;
 
-    const rewardsStateAfter = await miniNcn.account.rewardsState.fetch(pubkeys.rewardsState);
+    const ballot = keccak_256.digest(Buffer.concat([
+      Buffer.from("stake_rewards"),
+      stakeRewardsMint.toBuffer(),
+      amountBuffer,
+    ]));
+
+    await miniNcn.methods
+      .vote({
+        choice: { approve: { ballot } },
+      })
+      .accountsPartial({
+        config: configPubkey,
+        proposal,
+        operatorAdmin: op1AdminKeypair.publicKey,
+        operator: op1Pubkey,
+      })
+      .signers([op1AdminKeypair])
+      .rpc();
+
+    await miniNcn.methods
+      .checkConsensus()
+      .accounts({
+        config: configPubkey,
+        proposal,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    await miniNcn.methods
+      .addRewardsMint()
+      .accounts({
+        config: configPubkey,
+        rewardsMint: stakeRewardsMint,
+        authority: authority.publicKey,
+        rewardsTokenProgram: spl.TOKEN_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc();
+
+    const fundTokenAccount = await spl.getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      stakeRewardsMint,
+      authority.publicKey,
+    )
+    await spl.mintTo(
+      provider.connection,
+      provider.wallet.payer,
+      stakeRewardsMint,
+      fundTokenAccount.address,
+      authority,
+      amount,
+    )
+
+    const initTx = miniNcn.methods
+      .initializeStakeDistribution()
+      .accounts({
+        config: configPubkey,
+        proposal,
+        rewardsMint: stakeRewardsMint,
+        fundTokenAccount: fundTokenAccount.address,
+        funder: authority.publicKey,
+        rewardsTokenProgram: spl.TOKEN_PROGRAM_ID,
+      })
+      .signers([authority])
+
+    const { stakeDistribution } = await initTx.pubkeys();
+    await initTx.rpc();
+
+    const vaultTokenAccount = anchor.utils.token.associatedAddress({
+      mint: stakeRewardsMint,
+      owner: vaultPubkey,
+    });
+    const vaultBalanceBefore = (await spl.getAccount(provider.connection, vaultTokenAccount)).amount;
+
+    const vrtMint = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('vrt_mint'), stakeRewardsMint.toBuffer()],
+      miniNcn.programId
+    )[0];
+    // the vault admin is the fee wallet of the vault
+    const vaultFeeTokenAccount = await spl.getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      vrtMint,
+      vaultAdminPubkey,
+      true,
+    )
+
+    const operators = [
+      { operator: op0Pubkey, admin: op0AdminKeypair.publicKey, stakeWeight: 234567890n, feeBps: 1000n },
+      { operator: op1Pubkey, admin: op1AdminKeypair.publicKey, stakeWeight: 1000000000n, feeBps: 2000n },
+    ];
+    let vaultRewards = 0n;
+    for (const { operator, admin, stakeWeight, feeBps } of operators) {
+      // any token account of the operator admin receives the fee
+      const operatorFeeTokenAccount = await spl.createAccount(
+        provider.connection,
+        provider.wallet.payer,
+        stakeRewardsMint,
+        admin,
+        web3.Keypair.generate(),
+      )
+
+      await miniNcn.methods
+        .splitStakeRewards()
+        .accountsPartial({
+          config: configPubkey,
+          stakeDistribution,
+          operator,
+          vault: vaultPubkey,
+          vrtMint,
+          vaultFeeTokenAccount: vaultFeeTokenAccount.address,
+          rewardsMint: stakeRewardsMint,
+          operatorFeeTokenAccount,
+          vaultTokenAccount,
+          rewardsTokenProgram: spl.TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      const share = amount * stakeWeight / 1234567890n;
+      const fee = share * feeBps / 10000n;
+      vaultRewards += share - fee;
+
+      // one split per operator and vault, weighted by what that vault delegated
+      const [operatorRewardsPubkey] = web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("operator_rewards"), stakeDistribution.toBuffer(), operator.toBuffer(), vaultPubkey.toBuffer()],
+        miniNcn.programId,
+      );
+      const operatorRewards = await miniNcn.account.operatorRewards.fetch(operatorRewardsPubkey);
+      assert.ok(operatorRewards.vault.equals(vaultPubkey));
+      assert.equal(operatorRewards.stakeWeight.toString(), stakeWeight.toString());
+
+      const operatorFeeTokenAccountInfo = await spl.getAccount(provider.connection, operatorFeeTokenAccount);
+      assert.equal(operatorFeeTokenAccountInfo.amount, fee);
+    }
+
+    const vaultBalanceAfter = (await spl.getAccount(provider.connection, vaultTokenAccount)).amount;
+    assert.equal(vaultBalanceAfter - vaultBalanceBefore, vaultRewards);
+
+    // update_vault_balance counted the share as deposited tokens, backing the VRT
+    const { data: vaultData } = await provider.connection.getAccountInfo(vaultPubkey);
+    const tokensDeposited = vaultData.readBigUInt64LE(8 + 32 * 3 + 8);
+    assert.equal(tokensDeposited, vaultBalanceAfter);
+
+    const stakeDistributionAccount = await miniNcn.account.stakeDistribution.fetch(stakeDistribution);
+    assert.equal(stakeDistributionAccount.operatorsSplit.toNumber(), 2);
+    assert.equal(stakeDistributionAccount.vaultRewards.toString(), vaultRewards.toString());
+  })
+
+  it("route rewards to op1 and the vault", async () => {
+    const grossAmount = 1_000_000n;
+    // op1 charges 20%
+    const operatorFee = grossAmount * 2000n / 10000n;
+
+    // vaults take rewards in the token they hold
+    // created by the stake rewards split
+    const fundTokenAccount = anchor.utils.token.associatedAddress({
+      mint: stMint.publicKey,
+      owner: authority.publicKey,
+    });
+    await spl.mintTo(
+      provider.connection,
+      provider.wallet.payer,
+      stMint.publicKey,
+      fundTokenAccount,
+      authority,
+      grossAmount,
+    )
+
+    const operatorFeeTokenAccount = await spl.createAccount(
+      provider.connection,
+      provider.wallet.payer,
+      stMint.publicKey,
+      op1AdminKeypair.publicKey,
+      web3.Keypair.generate(),
+    )
+
+    const vrtMint = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('vrt_mint'), stMint.publicKey.toBuffer()],
+      miniNcn.programId
+    )[0];
+    // the vault admin is the fee wallet of the vault
+    const vaultFeeTokenAccount = await spl.getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      vrtMint,
+      vaultAdminPubkey,
+      true,
+    )
+    const vaultTokenAccount = anchor.utils.token.associatedAddress({
+      mint: stMint.publicKey,
+      owner: vaultPubkey,
+    });
+    const vaultBalanceBefore = (await spl.getAccount(provider.connection, vaultTokenAccount)).amount;
+
+    const [vaultOperatorDelegation] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_operator_delegation"), vaultPubkey.toBuffer(), op1Pubkey.toBuffer()],
+      JITO_VAULT_ID,
+    );
+
+    const signature = await miniNcn.methods
+      .routeRewards(new BN(grossAmount.toString()))
+      .accountsPartial({
+        config: configPubkey,
+        operator: op1Pubkey,
+        vaultOperatorDelegation,
+        vault: vaultPubkey,
+        vrtMint,
+        vaultFeeTokenAccount: vaultFeeTokenAccount.address,
+        rewardsMint: stMint.publicKey,
+        fundTokenAccount,
+        funder: authority.publicKey,
+        operatorFeeTokenAccount,
+        vaultTokenAccount,
+        authority: authority.publicKey,
+        rewardsTokenProgram: spl.TOKEN_PROGRAM_ID,
+      })
+      .signers([authority])
+      .rpc({ commitment: "confirmed" });
+
+    const operatorFeeTokenAccountInfo = await spl.getAccount(provider.connection, operatorFeeTokenAccount);
+    assert.equal(operatorFeeTokenAccountInfo.amount, operatorFee);
+
+    const vaultBalanceAfter = (await spl.getAccount(provider.connection, vaultTokenAccount)).amount;
+    assert.equal(vaultBalanceAfter - vaultBalanceBefore, grossAmount - operatorFee);
+
+    const fundTokenAccountInfo = await spl.getAccount(provider.connection, fundTokenAccount);
+    assert.equal(fundTokenAccountInfo.amount, 0n);
+
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const eventParser = new anchor.EventParser(miniNcn.programId, new anchor.BorshCoder(miniNcn.idl));
+    const events = [...eventParser.parseLogs(tx.meta.logMessages)];
+    const routed = events.find(({ name }) => name === "rewardsRouted");
+    assert.ok(routed, "RewardsRouted is emitted");
+    assert.ok(routed.data.operator.equals(op1Pubkey));
+    assert.ok(routed.data.vault.equals(vaultPubkey));
+    assert.equal(routed.data.grossAmount.toString(), grossAmount.toString());
+    assert.equal(routed.data.operatorFee.toString(), operatorFee.toString());
+    assert.equal(routed.data.vaultRewards.toString(), (grossAmount - operatorFee).toString());
+  })
+
+  it("record missed votes", async () => {
+    for (const proposal of [proposalPubkey, messageProposalPubkey, noticeProposalPubkey]) {
+      await miniNcn.methods
+        .recordMissedVotes()
+        .accountsPartial({
+          config: configPubkey,
+          proposal,
+          operator: op0Pubkey,
+        })
+        .rpc();
+    }
+
+    const [voterStatePubkey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("voter_state"), configPubkey.toBuffer(), op0Pubkey.toBuffer()],
+      miniNcn.programId,
+    );
+    const voterState = await miniNcn.account.voterState.fetch(voterStatePubkey);
+    assert.equal(voterState.recordedProposals.toNumber(), 3);
+    assert.equal(voterState.epochsParticipated.toNumber(), 1);
+    assert.equal(voterState.votesAgreeing.toNumber(), 1);
+    assert.equal(voterState.missedVotes.toNumber(), 2);
+    assert.isAbove(voterState.consecutiveMissedEpochs.toNumber(), 0);
+    assert.equal(voterState.participatedStakeWeight.toNumber(), 234567890);
+    assert.equal(voterState.eligibleStakeWeight.toNumber(), 234567890 * 3);
+  })
+
+  let openProposalPubkey: web3.PublicKey;
+
+  const setRole = (role: { proposer: {} } | { rewardsAdmin: {} }, holder: web3.PublicKey | null) => miniNcn.methods
+    .setRole(role, holder)
+    .accountsPartial({
+      config: configPubkey,
+      authority: authority.publicKey,
+    })
+    .signers([authority])
+    .rpc();
+
+  it("propose a message nobody finalizes", async () => {
+    await setRole({ proposer: {} }, userKeypair.publicKey);
+
+    const propose = (proposer: web3.Keypair) => miniNcn.methods
+      .propose({
+        kind: { message: [Array.from(Buffer.alloc(32, 2))] },
+        votingEpochs: new BN(1),
+        consensusThresholdBps: null,
+      })
+      .accounts({
+        config: configPubkey,
+        epochSnapshot: epochSnapshotPubkey,
+        authority: proposer.publicKey,
+      })
+      .signers([proposer])
+
+    // only the proposer or the authority may propose
+    try {
+      await propose(op0AdminKeypair).rpc();
+      assert.fail("op0 admin is not the proposer");
+    } catch (e) {
+      assert.include(e.toString(), "InvalidAuthority");
+    }
+
+    const tx = propose(userKeypair);
+    const { proposal } = await tx.pubkeys();
+    await tx.rpc();
+    openProposalPubkey = proposal;
+
+    await setRole({ proposer: {} }, null);
+
+    const recordMissedVotes = (proposal: web3.PublicKey) => miniNcn.methods
+      .recordMissedVotes()
+      .accountsPartial({
+        config: configPubkey,
+        proposal,
+        operator: op0Pubkey,
+      })
+      .rpc();
+
+    await recordMissedVotes(stakeRewardsProposalPubkey);
+
+    // its window is still open
+    try {
+      await recordMissedVotes(openProposalPubkey);
+      assert.fail("proposal is still open");
+    } catch (e) {
+      assert.include(e.toString(), "ProposalNotFinalized");
+    }
+  })
+
+  let distributionPubkey: web3.PublicKey;
+  let fundAmount: bigint;
+
+  it("initialize distribution", async () => {
+    fundAmount = userRewards
+      .filter(({ mint }) => mint.equals(rewardsMint.publicKey))
+      .reduce((sum, { amount }) => sum + amount, 0n);
+
+    const { epoch } = await provider.connection.getEpochInfo();
+    const tx = miniNcn.methods
+      .initializeDistribution(new BN(fundAmount.toString()), new BN(epoch + 10))
+      .accounts({
+        config: configPubkey,
+        proposal: proposalPubkey,
+        rewardsMint: rewardsMint.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+
+    const pubkeys = await tx.pubkeys();
+    debugPubkeys(pubkeys);
+    await tx.rpc();
+    distributionPubkey = pubkeys.distribution;
+
+    const distribution = await miniNcn.account.distribution.fetch(distributionPubkey);
+    assert.deepEqual(distribution.root, Array.from(rewardsTree.root));
+    assert.deepEqual(distribution.hashVersion, { v2: {} });
+    assert.equal(distribution.totalAmount.toString(), fundAmount.toString());
+  })
+
+  it("fund rewards", async () => {
+    await spl.mintToChecked(
+      provider.connection,
+      provider.wallet.payer,
+      rewardsMint.publicKey,
+      funderTokenAccount,
+      authority,
+      1000_000_000_000,
+      9,
+      [],
+      null,
+      rewardsTokenProgram,
+    )
+
+    const tx = miniNcn.methods
+      .fundRewards(new BN(fundAmount.toString()))
+      .accounts({
+        config: configPubkey,
+        distribution: distributionPubkey,
+        rewardsMint: rewardsMint.publicKey,
+        funder: authority.publicKey,
+        fundTokenAccount: funderTokenAccount,
+        rewardsTokenProgram,
+      })
+      .signers([authority])
+
+    debugPubkeys(await tx.pubkeys());
+    await tx.rpc();
+
+    const rewardsTokenAccountInfo = await spl.getAccount(provider.connection, rewardsTokenAccount);
+    assert.equal(rewardsTokenAccountInfo.amount, fundAmount);
+
+    const distribution = await miniNcn.account.distribution.fetch(distributionPubkey);
+    assert.equal(distribution.fundedAmount.toString(), fundAmount.toString());
+  })
+
+  it("pause stops propose, vote and claim", async () => {
+    const setPaused = (paused: boolean) => miniNcn.methods
+      .setPaused(paused)
+      .accountsPartial({
+        config: configPubkey,
+        authority: userKeypair.publicKey,
+      })
+      .signers([userKeypair])
+      .rpc();
+
+    await setPaused(true);
+
+    const calls = {
+      propose: miniNcn.methods
+        .propose({
+          kind: { message: [Array.from(Buffer.alloc(32, 3))] },
+          votingEpochs: new BN(1),
+          consensusThresholdBps: null,
+        })
+        .accounts({
+          config: configPubkey,
+          epochSnapshot: epochSnapshotPubkey,
+          authority: authority.publicKey,
+        })
+        .signers([authority]),
+      // op0 has not voted on the message proposal
+      vote: miniNcn.methods
+        .vote({ choice: { abstain: {} } })
+        .accountsPartial({
+          config: configPubkey,
+          proposal: messageProposalPubkey,
+          operatorAdmin: op0AdminKeypair.publicKey,
+          operator: op0Pubkey,
+        })
+        .signers([op0AdminKeypair]),
+      claim: miniNcn.methods
+        .claimRewards({
+          index: 0,
+          totalRewards: new BN(userRewards[0].amount.toString()),
+          proof: rewardsTree.getProof(0).proof.map(node => Array.from(node)),
+        })
+        .accounts({
+          config: configPubkey,
+          distribution: distributionPubkey,
+          rewardsMint: rewardsMint.publicKey,
+          owner: op0AdminKeypair.publicKey,
+          beneficiaryTokenAccount: funderTokenAccount,
+          rewardsTokenProgram,
+        })
+        .signers([op0AdminKeypair]),
+    };
+
+    for (const [name, call] of Object.entries(calls)) {
+      try {
+        await call.rpc();
+        assert.fail(`${name} while paused`);
+      } catch (e) {
+        assert.include(e.toString(), "Paused", name);
+      }
+    }
+
+    await setPaused(false);
+  });
+
+  it("claim rewards for op0", async () => {
+    const { proof, leafIndex, root } = rewardsTree.getProof(0)
+
+    const distribution = await miniNcn.account.distribution.fetch(distributionPubkey);
+    assert.deepEqual(distribution.root, Array.from(root));
+
+    const beneficiaryTokenAccount = await spl.createAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      rewardsMint.publicKey,
+      op0AdminKeypair.publicKey,
+      null,
+      rewardsTokenProgram,
+    )
+
+    const tx = miniNcn.methods
+      .claimRewards({
+        index: leafIndex,
+        totalRewards: new BN(userRewards[leafIndex].amount.toString()),
+        proof: proof.map(node => Array.from(node)),
+      })
+      .accounts({
+        config: configPubkey,
+        distribution: distributionPubkey,
+        rewardsMint: rewardsMint.publicKey,
+        owner: op0AdminKeypair.publicKey,
+        beneficiaryTokenAccount,
+        rewardsTokenProgram,
+      })
+      .signers([op0AdminKeypair])
+
+    const pubkeys = await tx.pubkeys();
+    debugPubkeys(pubkeys);
+
+    await tx.rpc();
+
+    const rewardsStateAfter = await miniNcn.account.rewardsState.fetch(pubkeys.rewardsState);
     assert.equal(rewardsStateAfter.claimedRewards.toString(), userRewards[leafIndex].amount.toString());
 
     const rewardsTokenAccountInfo = await spl.getAccount(provider.connection, rewardsTokenAccount);
@@ -524,5 +1479,571 @@ describe("mini-ncn", () => {
 
     const beneficiaryTokenAccountInfo = await spl.getAccount(provider.connection, beneficiaryTokenAccount);
     assert.equal(beneficiaryTokenAccountInfo.amount, userRewards[leafIndex].amount);
+
+    const distributionAfter = await miniNcn.account.distribution.fetch(distributionPubkey);
+    assert.equal(distributionAfter.claimedAmount.toString(), userRewards[leafIndex].amount.toString());
+  });
+
+
+  it("claim rewards for op1 to its delegate", async () => {
+    await miniNcn.methods
+      .setClaimDelegate(userKeypair.publicKey)
+      .accounts({
+        config: configPubkey,
+        owner: op1AdminKeypair.publicKey,
+      })
+      .signers([op1AdminKeypair])
+      .rpc();
+
+    const beneficiaryTokenAccount = await spl.createAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      rewardsMint.publicKey,
+      userKeypair.publicKey,
+      null,
+      rewardsTokenProgram,
+    )
+
+    // anyone can crank the claim, the owner does not sign
+    const { proof, leafIndex } = rewardsTree.getProof(1)
+    await miniNcn.methods
+      .claimRewardsFor({
+        index: leafIndex,
+        totalRewards: new BN(userRewards[leafIndex].amount.toString()),
+        proof: proof.map(node => Array.from(node)),
+      })
+      .accounts({
+        config: configPubkey,
+        distribution: distributionPubkey,
+        rewardsMint: rewardsMint.publicKey,
+        owner: op1AdminKeypair.publicKey,
+        beneficiaryTokenAccount,
+        rewardsTokenProgram,
+      })
+      .rpc();
+
+    const beneficiaryTokenAccountInfo = await spl.getAccount(provider.connection, beneficiaryTokenAccount);
+    assert.equal(beneficiaryTokenAccountInfo.amount, userRewards[leafIndex].amount);
+
+    const distribution = await miniNcn.account.distribution.fetch(distributionPubkey);
+    assert.equal(distribution.claimedAmount.toString(), fundAmount.toString());
+  });
+
+  let solDistributionPubkey: web3.PublicKey;
+
+  it("claim SOL rewards for op0", async () => {
+    const solAmount = userRewards[2].amount;
+    // funded twice what is claimed, the rest is clawed back once expired
+    const fundSolAmount = solAmount * 2n;
+    const { epoch } = await provider.connection.getEpochInfo();
+    const tx = miniNcn.methods
+      .initializeSolDistribution(new BN(fundSolAmount.toString()), new BN(epoch + 1))
+      .accounts({
+        config: configPubkey,
+        proposal: proposalPubkey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+
+    const pubkeys = await tx.pubkeys();
+    debugPubkeys(pubkeys);
+    await tx.rpc();
+    solDistributionPubkey = pubkeys.distribution;
+
+    // bitmap claims only pay out tokens
+    try {
+      await miniNcn.methods
+        .initializeClaimBitmap(4)
+        .accounts({
+          config: configPubkey,
+          distribution: pubkeys.distribution,
+          authority: authority.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+      assert.fail("SOL distributions have no claim bitmap");
+    } catch (e) {
+      assert.include(e.toString(), "InvalidRewardsMint");
+    }
+
+    await miniNcn.methods
+      .fundRewardsSol(new BN(fundSolAmount.toString()))
+      .accounts({
+        config: configPubkey,
+        distribution: pubkeys.distribution,
+        funder: provider.wallet.publicKey,
+      })
+      .rpc();
+
+    const { proof, leafIndex } = rewardsTree.getProof(2)
+    const balanceBefore = await provider.connection.getBalance(op0AdminKeypair.publicKey);
+    await miniNcn.methods
+      .claimRewardsSol({
+        index: leafIndex,
+        totalRewards: new BN(solAmount.toString()),
+        proof: proof.map(node => Array.from(node)),
+      })
+      .accounts({
+        config: configPubkey,
+        distribution: pubkeys.distribution,
+        owner: op0AdminKeypair.publicKey,
+        beneficiary: op0AdminKeypair.publicKey,
+      })
+      .signers([op0AdminKeypair])
+      .rpc();
+
+    const balanceAfter = await provider.connection.getBalance(op0AdminKeypair.publicKey);
+    assert.equal(BigInt(balanceAfter - balanceBefore), solAmount);
+
+    const distribution = await miniNcn.account.distribution.fetch(pubkeys.distribution);
+    assert.equal(distribution.claimedAmount.toString(), solAmount.toString());
+  });
+
+  it("rewards admin role", async () => {
+    await setRole({ rewardsAdmin: {} }, userKeypair.publicKey);
+
+    const clawback = (rewardsAdmin: web3.Keypair) => miniNcn.methods
+      .clawbackSol()
+      .accounts({
+        config: configPubkey,
+        distribution: solDistributionPubkey,
+        treasury: authority.publicKey,
+        authority: rewardsAdmin.publicKey,
+      })
+      .signers([rewardsAdmin])
+      .rpc();
+
+    // the rewards admin gets past the access check, the distribution is still live
+    try {
+      await clawback(userKeypair);
+      assert.fail("distribution has not expired");
+    } catch (e) {
+      assert.include(e.toString(), "DistributionNotExpired");
+    }
+
+    try {
+      await clawback(op0AdminKeypair);
+      assert.fail("op0 admin is not the rewards admin");
+    } catch (e) {
+      assert.include(e.toString(), "InvalidAuthority");
+    }
+
+    await setRole({ rewardsAdmin: {} }, null);
+  });
+
+  let bitmapDistributionPubkey: web3.PublicKey;
+
+  it("claim rewards through a claim bitmap", async () => {
+    const leafIndex = 3;
+    const amount = userRewards[leafIndex].amount;
+    const { proof } = rewardsTree.getProof(leafIndex)
+
+    await miniNcn.methods
+      .addRewardsMint()
+      .accounts({
+        config: configPubkey,
+        rewardsMint: bitmapMint.publicKey,
+        authority: authority.publicKey,
+        rewardsTokenProgram,
+      })
+      .signers([authority])
+      .rpc();
+
+    const { epoch } = await provider.connection.getEpochInfo();
+    const tx = miniNcn.methods
+      .initializeDistribution(new BN(amount.toString()), new BN(epoch + 10))
+      .accounts({
+        config: configPubkey,
+        proposal: proposalPubkey,
+        rewardsMint: bitmapMint.publicKey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+
+    const pubkeys = await tx.pubkeys();
+    debugPubkeys(pubkeys);
+    await tx.rpc();
+    bitmapDistributionPubkey = pubkeys.distribution;
+
+    // twice the leaves the tree holds, so only the proof bounds the index
+    await miniNcn.methods
+      .initializeClaimBitmap(2 ** (proof.length + 1))
+      .accounts({
+        config: configPubkey,
+        distribution: bitmapDistributionPubkey,
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    const fundTokenAccount = await spl.createAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      bitmapMint.publicKey,
+      authority.publicKey,
+      null,
+      rewardsTokenProgram,
+    )
+    await spl.mintToChecked(
+      provider.connection,
+      provider.wallet.payer,
+      bitmapMint.publicKey,
+      fundTokenAccount,
+      authority,
+      amount,
+      9,
+      [],
+      null,
+      rewardsTokenProgram,
+    )
+    await miniNcn.methods
+      .fundRewards(new BN(amount.toString()))
+      .accounts({
+        config: configPubkey,
+        distribution: bitmapDistributionPubkey,
+        rewardsMint: bitmapMint.publicKey,
+        funder: authority.publicKey,
+        fundTokenAccount,
+        rewardsTokenProgram,
+      })
+      .signers([authority])
+      .rpc();
+
+    const beneficiaryTokenAccount = await spl.createAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      bitmapMint.publicKey,
+      op1AdminKeypair.publicKey,
+      null,
+      rewardsTokenProgram,
+    )
+
+    const [claimBitmap] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("claim_bitmap"), bitmapDistributionPubkey.toBuffer()],
+      miniNcn.programId,
+    );
+    const claim = (index: number) => miniNcn.methods
+      .claimRewardsWithBitmap({
+        index,
+        totalRewards: new BN(amount.toString()),
+        proof: proof.map(node => Array.from(node)),
+      })
+      .accounts({
+        config: configPubkey,
+        distribution: bitmapDistributionPubkey,
+        claimBitmap,
+        rewardsMint: bitmapMint.publicKey,
+        owner: op1AdminKeypair.publicKey,
+        beneficiaryTokenAccount,
+        rewardsTokenProgram,
+      })
+      .signers([op1AdminKeypair])
+      .rpc();
+
+    await claim(leafIndex);
+
+    const beneficiaryTokenAccountInfo = await spl.getAccount(provider.connection, beneficiaryTokenAccount);
+    assert.equal(beneficiaryTokenAccountInfo.amount, amount);
+
+    try {
+      await claim(leafIndex);
+      assert.fail("leaf is already claimed");
+    } catch (e) {
+      assert.include(e.toString(), "AlreadyClaimed");
+    }
+
+    // the proof only reads the low depth bits, the same proof must not claim another leaf
+    try {
+      await claim(leafIndex + 2 ** proof.length);
+      assert.fail("index is out of the tree");
+    } catch (e) {
+      assert.include(e.toString(), "InvalidLeafIndex");
+    }
+
+    // cranked bitmap claims pay op1's delegate, and share the bitmap with direct claims
+    const claimFor = (beneficiaryTokenAccount: web3.PublicKey) => miniNcn.methods
+      .claimRewardsWithBitmapFor({
+        index: leafIndex,
+        totalRewards: new BN(amount.toString()),
+        proof: proof.map(node => Array.from(node)),
+      })
+      .accounts({
+        config: configPubkey,
+        distribution: bitmapDistributionPubkey,
+        claimBitmap,
+        rewardsMint: bitmapMint.publicKey,
+        owner: op1AdminKeypair.publicKey,
+        beneficiaryTokenAccount,
+        rewardsTokenProgram,
+      })
+      .rpc();
+
+    try {
+      await claimFor(beneficiaryTokenAccount);
+      assert.fail("op1 has a claim delegate");
+    } catch (e) {
+      assert.include(e.toString(), "InvalidBeneficiary");
+    }
+
+    const delegateTokenAccount = await spl.createAssociatedTokenAccount(
+      provider.connection,
+      provider.wallet.payer,
+      bitmapMint.publicKey,
+      userKeypair.publicKey,
+      null,
+      rewardsTokenProgram,
+    )
+    try {
+      await claimFor(delegateTokenAccount);
+      assert.fail("leaf is already claimed");
+    } catch (e) {
+      assert.include(e.toString(), "AlreadyClaimed");
+    }
   });
+
+  const clawbackSol = () => miniNcn.methods
+    .clawbackSol()
+    .accounts({
+      config: configPubkey,
+      distribution: solDistributionPubkey,
+      treasury: authority.publicKey,
+      authority: authority.publicKey,
+    })
+    .signers([authority])
+    .rpc();
+
+  it("clawback before expiry fails", async () => {
+    const config = await miniNcn.account.config.fetch(configPubkey);
+    assert.equal(config.treasury.toBase58(), authority.publicKey.toBase58());
+
+    try {
+      await clawbackSol();
+      assert.fail("SOL distribution has not expired");
+    } catch (e) {
+      assert.include(e.toString(), "DistributionNotExpired");
+    }
+
+    try {
+      await miniNcn.methods
+        .clawback()
+        .accounts({
+          config: configPubkey,
+          distribution: distributionPubkey,
+          rewardsMint: rewardsMint.publicKey,
+          treasuryTokenAccount: funderTokenAccount,
+          authority: authority.publicKey,
+          rewardsTokenProgram,
+        })
+        .signers([authority])
+        .rpc();
+      assert.fail("distribution has not expired");
+    } catch (e) {
+      assert.include(e.toString(), "DistributionNotExpired");
+    }
+  });
+
+  const ncnAdminPubkey = () => web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("ncn_admin"), ncnPubkey.toBuffer()],
+    miniNcn.programId,
+  )[0];
+
+  const ncnVaultSlasherTicket = () => web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("ncn_slasher_ticket"), ncnPubkey.toBuffer(), vaultPubkey.toBuffer(), ncnAdminPubkey().toBuffer()],
+    JITO_RESTAKING_ID,
+  )[0];
+
+  const vaultNcnSlasherTicket = () => web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("vault_slasher_ticket"), vaultPubkey.toBuffer(), ncnPubkey.toBuffer(), ncnAdminPubkey().toBuffer()],
+    JITO_VAULT_ID,
+  )[0];
+
+  it("register slasher", async () => {
+    const tx = miniNcn.methods
+      .registerSlasher(new BN(1500))
+      .accountsPartial({
+        config: configPubkey,
+        ncn: ncnPubkey,
+        vault: vaultPubkey,
+        vaultSlasherAdmin: vaultAdminPubkey,
+        ncnVaultSlasherTicket: ncnVaultSlasherTicket(),
+        vaultNcnSlasherTicket: vaultNcnSlasherTicket(),
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+
+    debugPubkeys(await tx.pubkeys());
+    await tx.rpc();
+
+    // slasher tickets follow the same warmup as the other tickets
+    await nextEpoch();
+
+    await miniNcn.methods
+      .warmupSlasher()
+      .accountsPartial({
+        config: configPubkey,
+        ncn: ncnPubkey,
+        vault: vaultPubkey,
+        vaultSlasherAdmin: vaultAdminPubkey,
+        ncnVaultSlasherTicket: ncnVaultSlasherTicket(),
+        vaultNcnSlasherTicket: vaultNcnSlasherTicket(),
+        authority: authority.publicKey,
+      })
+      .signers([authority])
+      .rpc();
+
+    await nextEpoch();
+  })
+
+  // the vault program derives its epoch from the slot and its own epoch length
+  const vaultEpoch = async () => {
+    const [vaultConfig] = web3.PublicKey.findProgramAddressSync([Buffer.from("config")], JITO_VAULT_ID);
+    const { data } = await provider.connection.getAccountInfo(vaultConfig);
+    const epochLength = data.readBigUInt64LE(8 + 32 + 32);
+    return BigInt(await provider.connection.getSlot()) / epochLength;
+  }
+
+  const forceUndelegate = async (
+    proposal: web3.PublicKey,
+    operator: web3.PublicKey,
+    amount: number,
+    signer = authority,
+    ticketEpoch?: bigint,
+  ) => {
+    const [vaultNcnSlasherOperatorTicket] = web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("vault_ncn_slasher_operator"),
+        vaultPubkey.toBuffer(),
+        ncnPubkey.toBuffer(),
+        ncnAdminPubkey().toBuffer(),
+        operator.toBuffer(),
+        new BN((ticketEpoch ?? await vaultEpoch()).toString()).toArrayLike(Buffer, "le", 8),
+      ],
+      JITO_VAULT_ID,
+    );
+
+    return miniNcn.methods
+      .forceUndelegate(new BN(amount))
+      .accountsPartial({
+        config: configPubkey,
+        proposal,
+        undelegationProposal: null,
+        authority: signer === authority ? authority.publicKey : null,
+        ncn: ncnPubkey,
+        vault: vaultPubkey,
+        operator,
+        vaultNcnSlasherTicket: vaultNcnSlasherTicket(),
+        vaultNcnSlasherOperatorTicket,
+        payer: signer.publicKey,
+      })
+      .signers([signer])
+  }
+
+  it("force op0 to undelegate for not voting", async () => {
+    // op1 voted for the outcome
+    try {
+      await (await forceUndelegate(noticeProposalPubkey, op1Pubkey, 1000)).rpc();
+      assert.fail("op1 should not be penalized");
+    } catch (e) {
+      assert.include(e.toString(), "OperatorVotedForOutcome");
+    }
+
+    // without an approved proposal only the authority can force it
+    try {
+      await (await forceUndelegate(noticeProposalPubkey, op0Pubkey, 1000, op1AdminKeypair)).rpc();
+      assert.fail("only the authority can force an undelegation");
+    } catch (e) {
+      assert.include(e.toString(), "InvalidAuthority");
+    }
+
+    // the operator ticket is the one of the current vault epoch
+    try {
+      await (await forceUndelegate(noticeProposalPubkey, op0Pubkey, 1000, authority, await vaultEpoch() + 1n)).rpc();
+      assert.fail("ticket of another vault epoch");
+    } catch (e) {
+      assert.include(e.toString(), "InvalidSlasherOperatorTicket");
+    }
+
+    // op0 did not vote on the stake rewards proposal either. both undelegations
+    // share one transaction so they fall in the same vault epoch
+    const stakeRewardsUndelegation = async (amount: number) =>
+      (await forceUndelegate(stakeRewardsProposalPubkey, op0Pubkey, amount)).instruction();
+
+    try {
+      await (await forceUndelegate(noticeProposalPubkey, op0Pubkey, 1000))
+        .postInstructions([await stakeRewardsUndelegation(1000)])
+        .rpc();
+      assert.fail("2000 exceeds the cap of 1500");
+    } catch (e) {
+      assert.include(e.toString(), "UndelegationCapExceeded");
+    }
+
+    const tx = (await forceUndelegate(noticeProposalPubkey, op0Pubkey, 1000))
+      .postInstructions([await stakeRewardsUndelegation(500)]);
+    const pubkeys = await tx.pubkeys();
+    debugPubkeys(pubkeys);
+    await tx.rpc();
+
+    const undelegationRecord = await miniNcn.account.undelegationRecord.fetch(pubkeys.undelegationRecord);
+    assert.ok(undelegationRecord.operator.equals(op0Pubkey));
+    assert.equal(undelegationRecord.amount.toNumber(), 1000);
+    assert.equal(undelegationRecord.voted, false);
+    assert.ok(undelegationRecord.vault.equals(vaultPubkey));
+
+    const undelegationTally = await miniNcn.account.undelegationTally.fetch(pubkeys.undelegationTally);
+    assert.equal(undelegationTally.amount.toNumber(), 1500);
+    assert.ok(undelegationRecord.epoch.eq(undelegationTally.epoch));
+  })
+
+  // the SOL distribution expired while the slasher tickets warmed up
+  it("clawback SOL after expiry", async () => {
+    const distribution = await miniNcn.account.distribution.fetch(solDistributionPubkey);
+    const unclaimed = distribution.fundedAmount.sub(distribution.claimedAmount).toNumber();
+    assert.ok(unclaimed > 0);
+
+    const balanceBefore = await provider.connection.getBalance(authority.publicKey);
+    await clawbackSol();
+    const balanceAfter = await provider.connection.getBalance(authority.publicKey);
+    // the provider wallet pays the fees
+    assert.equal(balanceAfter - balanceBefore, unclaimed);
+
+    const distributionAfter = await miniNcn.account.distribution.fetch(solDistributionPubkey);
+    assert.equal(distributionAfter.clawedBackAmount.toNumber(), unclaimed);
+
+    // lamports funded now could never be claimed
+    try {
+      await miniNcn.methods
+        .fundRewardsSol(new BN(1))
+        .accounts({
+          config: configPubkey,
+          distribution: solDistributionPubkey,
+          funder: provider.wallet.publicKey,
+        })
+        .rpc();
+      assert.fail("distribution is expired");
+    } catch (e) {
+      assert.include(e.toString(), "DistributionFundingClosed");
+    }
+  })
+
+  it("record missed votes once the window ended", async () => {
+    const proposal = await miniNcn.account.proposal.fetch(openProposalPubkey);
+    assert.deepEqual(proposal.status, { voting: {} });
+
+    await miniNcn.methods
+      .recordMissedVotes()
+      .accountsPartial({
+        config: configPubkey,
+        proposal: openProposalPubkey,
+        operator: op0Pubkey,
+      })
+      .rpc();
+
+    const [voterStatePubkey] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("voter_state"), configPubkey.toBuffer(), op0Pubkey.toBuffer()],
+      miniNcn.programId,
+    );
+    const voterState = await miniNcn.account.voterState.fetch(voterStatePubkey);
+    assert.equal(voterState.recordedProposals.toNumber(), 5);
+    assert.equal(voterState.missedVotes.toNumber(), 4);
+  })
 });
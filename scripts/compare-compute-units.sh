@@ -0,0 +1,24 @@
+#!/usr/bin/env sh
+
+# Prints compute units per mini-ncn instruction before and after a change.
+# The current tests run against the program source of <baseline>, then of HEAD.
+# usage: scripts/compare-compute-units.sh <baseline>
+
+set -e
+
+BASELINE=${1:?usage: $0 <baseline>}
+
+cd "$(dirname "$0")/../mini-ncn"
+
+restore() {
+    git checkout HEAD -- programs/mini-ncn/src
+}
+trap restore EXIT
+
+# tests the older program lacks fail, the report covers the rest
+git checkout "$BASELINE" -- programs/mini-ncn/src
+anchor test || true
+cp target/compute-units.json target/compute-units-baseline.json
+
+restore
+CU_BASELINE=target/compute-units-baseline.json anchor test